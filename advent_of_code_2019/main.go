@@ -2,6 +2,7 @@ package main
 
 import (
 	"encoding/json"
+	"flag"
 	"fmt"
 	"io/ioutil"
 	"log"
@@ -24,6 +25,15 @@ func registerDay(title, description string, solveFunc func(string) (string, erro
 	days = append(days, Day{ID: fmt.Sprintf("%d", len(days)+1), Title: title, Description: description, solveFunc: solveFunc})
 }
 
+func findDay(id string) *Day {
+	for i := range days {
+		if days[i].ID == id {
+			return &days[i]
+		}
+	}
+	return nil
+}
+
 func list(w http.ResponseWriter, r *http.Request) {
 	if r.Method != "GET" {
 		http.Error(w, "Only GET is supported for /list", 400)
@@ -47,13 +57,7 @@ func solve(w http.ResponseWriter, r *http.Request) {
 
 	id := strings.Trim(strings.TrimPrefix(r.URL.Path, "/solve/"), "/")
 
-	var day *Day
-	for _, d := range days {
-		if d.ID == id {
-			day = &d
-			break
-		}
-	}
+	day := findDay(id)
 	if day == nil {
 		http.Error(w, fmt.Sprintf("ID %s did not match a day", id), 400)
 		return
@@ -91,6 +95,13 @@ func main() {
 	log.SetFlags(0)
 	log.SetOutput(new(logWriter))
 
+	stdio := flag.Bool("stdio", false, "Answer JSON lines on stdin/stdout instead of serving HTTP")
+	flag.Parse()
+	if *stdio {
+		serveStdio()
+		return
+	}
+
 	http.HandleFunc("/list/", list)
 	http.HandleFunc("/solve/", solve)
 	port := os.Getenv("CHALLENGES_AOC_2019_PORT")
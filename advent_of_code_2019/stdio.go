@@ -0,0 +1,120 @@
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"log"
+	"os"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Version of the JSON-lines protocol spoken with -stdio, one request per line on stdin
+// answered by one line on stdout
+const protocolVersion = 1
+
+type stdioRequest struct {
+	Type  string `json:"type"`
+	ID    string `json:"id"`
+	Input string `json:"input"`
+}
+
+type listResponse struct {
+	Version    int   `json:"version"`
+	Challenges []Day `json:"challenges"`
+}
+
+type part struct {
+	Name  string      `json:"name"`
+	Value interface{} `json:"value"`
+	Notes string      `json:"notes,omitempty"`
+}
+
+type solveResponse struct {
+	Version    int     `json:"version"`
+	Status     string  `json:"status"`
+	Parts      []part  `json:"parts,omitempty"`
+	Error      string  `json:"error,omitempty"`
+	DurationMs float64 `json:"duration_ms,omitempty"`
+}
+
+func solveError(message string) solveResponse {
+	return solveResponse{Version: protocolVersion, Status: "error", Error: message}
+}
+
+// parseParts splits the "Name: value" lines returned by the days into parts, other lines are
+// notes of the previous part
+func parseParts(solved string) []part {
+	parts := []part{}
+	for _, line := range strings.Split(solved, "\n") {
+		line = strings.TrimSpace(line)
+		if line == "" {
+			continue
+		}
+		split := strings.SplitN(line, ": ", 2)
+		switch {
+		case len(split) == 2:
+			var value interface{} = split[1]
+			if i, err := strconv.ParseInt(split[1], 10, 64); err == nil {
+				value = i
+			}
+			parts = append(parts, part{Name: split[0], Value: value})
+		case len(parts) > 0:
+			last := &parts[len(parts)-1]
+			if last.Notes != "" {
+				last.Notes += "\n"
+			}
+			last.Notes += line
+		default:
+			parts = append(parts, part{Name: "Result", Value: line})
+		}
+	}
+	return parts
+}
+
+func handleStdioRequest(line string) interface{} {
+	var request stdioRequest
+	if err := json.Unmarshal([]byte(line), &request); err != nil {
+		return solveError(fmt.Sprintf("Invalid request: %v", err))
+	}
+	switch request.Type {
+	case "list":
+		return listResponse{Version: protocolVersion, Challenges: days}
+	case "solve":
+		day := findDay(request.ID)
+		if day == nil {
+			return solveError(fmt.Sprintf("ID %s did not match a day", request.ID))
+		}
+		start := time.Now()
+		solved, err := day.solveFunc(request.Input)
+		durationMs := float64(time.Since(start).Microseconds()) / 1000
+		if err != nil {
+			response := solveError(fmt.Sprintf("Got an error while solving: %v", err))
+			response.DurationMs = durationMs
+			return response
+		}
+		return solveResponse{Version: protocolVersion, Status: "ok", Parts: parseParts(solved), DurationMs: durationMs}
+	default:
+		return solveError(fmt.Sprintf("Unknown request type %q", request.Type))
+	}
+}
+
+// serveStdio answers the requests read on stdin until it is closed
+func serveStdio() {
+	scanner := bufio.NewScanner(os.Stdin)
+	scanner.Buffer(make([]byte, 64*1024), 64*1024*1024)
+	encoder := json.NewEncoder(os.Stdout)
+	for scanner.Scan() {
+		if strings.TrimSpace(scanner.Text()) == "" {
+			continue
+		}
+		if err := encoder.Encode(handleStdioRequest(scanner.Text())); err != nil {
+			log.Fatal(err)
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		log.Fatal(err)
+	}
+}
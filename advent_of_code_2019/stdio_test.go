@@ -0,0 +1,43 @@
+package main
+
+import (
+	"encoding/json"
+	"testing"
+
+	"github.com/stretchr/testify/assert"
+)
+
+func TestParseParts(t *testing.T) {
+	var tests = []struct {
+		solved   string
+		expected []part
+	}{
+		{"Part 1: 2\nPart 2: abc", []part{{Name: "Part 1", Value: int64(2)}, {Name: "Part 2", Value: "abc"}}},
+		{"Part 1: 2\n#..#\n.##.", []part{{Name: "Part 1", Value: int64(2), Notes: "#..#\n.##."}}},
+		{"42", []part{{Name: "Result", Value: "42"}}},
+	}
+	for _, test := range tests {
+		assert.Equal(t, test.expected, parseParts(test.solved))
+	}
+}
+
+func TestHandleStdioRequest(t *testing.T) {
+	var tests = []struct {
+		request  string
+		expected string
+	}{
+		{`{"type": "solve", "version": 1, "id": "1", "input": "14"}`, `{"version":1,"status":"ok","parts":[{"name":"Part 1","value":2},{"name":"Part 2","value":2}]}`},
+		{`{"type": "solve", "version": 1, "id": "0", "input": ""}`, `{"version":1,"status":"error","error":"ID 0 did not match a day"}`},
+		{`{"type": "other"}`, `{"version":1,"status":"error","error":"Unknown request type \"other\""}`},
+	}
+	for _, test := range tests {
+		response := handleStdioRequest(test.request)
+		if solved, ok := response.(solveResponse); ok {
+			solved.DurationMs = 0
+			response = solved
+		}
+		marshalled, err := json.Marshal(response)
+		assert.NoError(t, err)
+		assert.Equal(t, test.expected, string(marshalled))
+	}
+}
@@ -0,0 +1,254 @@
+use std::{fmt, thread, time::Duration};
+
+use challenges_types::{
+    answers::ChallengeVerification,
+    bench::BenchReport,
+    groups::{Challenge, Group, GroupSummary, GroupUnavailableError, SearchResult},
+    jobs::Job,
+};
+use reqwest::{
+    blocking::{self, RequestBuilder, Response},
+    StatusCode, Url,
+};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The base URL given to the client can't be parsed
+    InvalidUrl(String),
+    /// The challenges of the group can't be listed, answered with a 502
+    Unavailable(GroupUnavailableError),
+    /// The group, the challenge, the job or the saved input doesn't exist
+    NotFound,
+    /// The solve didn't finish within the time limit of the server
+    TimedOut(String),
+    /// Any other failed response, with its status and body
+    Failed(StatusCode, String),
+    /// The request couldn't be sent or its response couldn't be read
+    Http(reqwest::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::InvalidUrl(message) => write!(f, "{}", message),
+            ClientError::Unavailable(e) => write!(f, "{}", e),
+            ClientError::NotFound => write!(f, "Not found"),
+            ClientError::TimedOut(message) => write!(f, "{}", message),
+            ClientError::Failed(status, body) => write!(f, "{}: {}", status, body),
+            ClientError::Http(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        return ClientError::Http(err);
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Blocking client of the API, whose responses are read into the types the server writes them
+/// from, so that a change of the API breaks the build of its clients rather than their runs.
+/// Requests made with the keys of the first versions of the API follow their redirects
+pub struct Client {
+    base_url: Url,
+    http: blocking::Client,
+}
+
+impl Client {
+    /// Client of the API under the URL, as `http://localhost:8081/api`
+    pub fn new(base_url: &str) -> Result<Self> {
+        let mut base_url = match Url::parse(base_url) {
+            Ok(u) => u,
+            Err(e) => {
+                return Err(ClientError::InvalidUrl(format!(
+                    "Invalid base URL {}: {}",
+                    base_url, e
+                )))
+            }
+        };
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        return Ok(Client {
+            base_url: base_url,
+            http: blocking::Client::new(),
+        });
+    }
+
+    /// URL of the path segments under the base URL, each of them being encoded
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .unwrap()
+            .pop_if_empty()
+            .extend(segments);
+        return url;
+    }
+
+    pub fn groups(&self) -> Result<Vec<GroupSummary>> {
+        return read_json(self.http.get(self.url(&["groups"])));
+    }
+
+    /// Lists the challenges of every group again
+    pub fn refresh(&self) -> Result<Vec<GroupSummary>> {
+        return read_json(self.http.post(self.url(&["refresh"])));
+    }
+
+    /// Challenges of the group, `None` if it doesn't exist
+    pub fn group(&self, group_key: &str) -> Result<Option<Group>> {
+        return found(read_json(self.http.get(self.url(&["groups", group_key]))));
+    }
+
+    pub fn challenge(&self, group_key: &str, challenge_key: &str) -> Result<Option<Challenge>> {
+        let request = self
+            .http
+            .get(self.url(&["groups", group_key, challenge_key]));
+        return found(read_json(request));
+    }
+
+    /// Queues the solve of the input, a single part when given, followed through `job` or `wait`
+    pub fn solve(
+        &self,
+        group_key: &str,
+        challenge_key: &str,
+        input: &str,
+        part: Option<usize>,
+    ) -> Result<Job> {
+        let request = self.solve_request(group_key, challenge_key, Some(input), part);
+        return read_json(request);
+    }
+
+    /// Queues the solve of the saved input of the challenge, a single part when given
+    pub fn solve_saved(
+        &self,
+        group_key: &str,
+        challenge_key: &str,
+        part: Option<usize>,
+    ) -> Result<Job> {
+        let request = self.solve_request(group_key, challenge_key, None, part);
+        return read_json(request);
+    }
+
+    /// Request solving the input, or the saved input when absent
+    fn solve_request(
+        &self,
+        group_key: &str,
+        challenge_key: &str,
+        input: Option<&str>,
+        part: Option<usize>,
+    ) -> RequestBuilder {
+        let mut url = self.url(&["groups", group_key, challenge_key, "solve"]);
+        if let Some(part) = part {
+            url.query_pairs_mut().append_pair("part", &part.to_string());
+        }
+        if input.is_none() {
+            url.query_pairs_mut().append_pair("use_saved", "true");
+        }
+        return self
+            .http
+            .post(url)
+            .header("Content-Type", "text/plain")
+            .body(input.unwrap_or_default().to_owned());
+    }
+
+    pub fn job(&self, id: u64) -> Result<Option<Job>> {
+        let request = self.http.get(self.url(&["jobs", &id.to_string()]));
+        return found(read_json(request));
+    }
+
+    /// Polls the job at the given interval until it is finished, `None` if it was forgotten
+    pub fn wait(&self, job: Job, interval: Duration) -> Result<Option<Job>> {
+        let mut job = job;
+        while !job.status.is_finished() {
+            thread::sleep(interval);
+            job = match self.job(job.id)? {
+                Some(j) => j,
+                None => return Ok(None),
+            };
+        }
+        return Ok(Some(job));
+    }
+
+    /// Cancels a queued or running job, finished jobs are left untouched
+    pub fn cancel_job(&self, id: u64) -> Result<Option<Job>> {
+        let request = self.http.delete(self.url(&["jobs", &id.to_string()]));
+        return found(read_json(request));
+    }
+
+    pub fn save_input(&self, group_key: &str, challenge_key: &str, input: &str) -> Result<()> {
+        let request = self
+            .http
+            .put(self.url(&["groups", group_key, challenge_key, "input"]))
+            .header("Content-Type", "text/plain")
+            .body(input.to_owned());
+        send(request)?;
+        return Ok(());
+    }
+
+    /// Solves the saved inputs `runs` times, optionally those of a single group
+    pub fn bench(&self, runs: usize, group_key: Option<&str>) -> Result<BenchReport> {
+        let mut url = self.url(&["bench"]);
+        url.query_pairs_mut().append_pair("runs", &runs.to_string());
+        if let Some(group_key) = group_key {
+            url.query_pairs_mut().append_pair("group", group_key);
+        }
+        return read_json(self.http.get(url));
+    }
+
+    /// Solves the saved inputs, optionally those of a single group, and compares the results
+    /// with the known answers
+    pub fn verify(&self, group_key: Option<&str>) -> Result<Vec<ChallengeVerification>> {
+        let mut url = self.url(&["verify"]);
+        if let Some(group_key) = group_key {
+            url.query_pairs_mut().append_pair("group", group_key);
+        }
+        return read_json(self.http.get(url));
+    }
+
+    pub fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let mut url = self.url(&["search"]);
+        url.query_pairs_mut().append_pair("q", query);
+        return read_json(self.http.get(url));
+    }
+
+    /// OpenAPI document of the API
+    pub fn openapi(&self) -> Result<serde_json::Value> {
+        return read_json(self.http.get(self.url(&["openapi.json"])));
+    }
+}
+
+/// Sends the request, failing on the error statuses of the API
+fn send(request: RequestBuilder) -> Result<Response> {
+    let response = request.send()?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text()?;
+    return Err(match status {
+        StatusCode::NOT_FOUND => ClientError::NotFound,
+        StatusCode::REQUEST_TIMEOUT => ClientError::TimedOut(body),
+        StatusCode::BAD_GATEWAY => match serde_json::from_str(&body) {
+            Ok(e) => ClientError::Unavailable(e),
+            Err(_) => ClientError::Failed(status, body),
+        },
+        _ => ClientError::Failed(status, body),
+    });
+}
+
+fn read_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T> {
+    return Ok(send(request)?.json()?);
+}
+
+/// Turns the 404 of a lookup into `None`
+fn found<T>(result: Result<T>) -> Result<Option<T>> {
+    return match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ClientError::NotFound) => Ok(None),
+        Err(e) => Err(e),
+    };
+}
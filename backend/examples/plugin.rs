@@ -0,0 +1,60 @@
+use anyhow::Result;
+use challenges::{
+    export_group,
+    groups::{
+        challenge_config::{ChallengeConfig, ChallengeError},
+        group_config::{GroupConfig, GroupUnavailableError},
+        solution::Solution,
+        solve_context::SolveContext,
+    },
+};
+
+/// Group built as a plugin, loaded by the backend from its plugins directory:
+/// `cargo build --example plugin`, then copy `target/debug/examples/libplugin.so`
+struct ExamplePlugin {}
+
+impl GroupConfig for ExamplePlugin {
+    fn name(&self) -> &str {
+        return "Example Plugin";
+    }
+    fn id(&self) -> String {
+        return "example-plugin".to_owned();
+    }
+    fn url(&self) -> &str {
+        return "https://github.com/julienduchesne/challenges";
+    }
+    fn challenges(&self) -> Result<Vec<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        return Ok(vec![Box::new(Sum {})]);
+    }
+}
+
+/// Sum and product of the numbers of the input
+struct Sum {}
+
+impl ChallengeConfig for Sum {
+    fn title(&self) -> &str {
+        return "Sum";
+    }
+
+    fn id(&self) -> String {
+        return "sum".to_owned();
+    }
+
+    fn description(&self) -> &str {
+        return "Adds, then multiplies, the numbers of the input";
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
+        let numbers = input
+            .split_whitespace()
+            .map(|n| n.parse::<i128>())
+            .collect::<Result<Vec<i128>, _>>()?;
+        return match part {
+            1 => Ok(Solution::new().with_part("Part 1", numbers.iter().sum::<i128>())),
+            2 => Ok(Solution::new().with_part("Part 2", numbers.iter().product::<i128>())),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
+    }
+}
+
+export_group!(|| ExamplePlugin {});
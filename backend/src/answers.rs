@@ -0,0 +1,152 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use anyhow::Result;
+pub use challenges_types::answers::{ChallengeVerification, PartStatus, PartVerification};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    groups::solution::PartValue,
+    input_store::{ChallengeKey, InputStore},
+};
+
+const ANSWERS_FILE: &str = "answers.json";
+
+/// Known answers, keyed by group id, challenge id and part name:
+/// `{"advent-of-code-2020": {"day-1-report-repair": {"Part 1": 514579}}}`. Answers keyed by the
+/// keys of the names of the first versions are still read
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(flatten)]
+    answers: HashMap<String, HashMap<String, HashMap<String, PartValue>>>,
+}
+
+impl Answers {
+    /// Loads the answers file, an absent file holds no answers
+    pub fn load(path: &Path) -> Result<Self> {
+        return match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        };
+    }
+
+    /// Answers file stored alongside the saved inputs, or $CHALLENGES_ANSWERS_FILE if set
+    pub fn from_env(store: &InputStore) -> Result<Self> {
+        return match std::env::var("CHALLENGES_ANSWERS_FILE") {
+            Ok(path) => Answers::load(Path::new(&path)),
+            Err(_) => Answers::load(&store.root().join(ANSWERS_FILE)),
+        };
+    }
+
+    pub fn expected(&self, key: &ChallengeKey) -> Option<&HashMap<String, PartValue>> {
+        let (group_key, challenge_key) = key.legacy_keys();
+        return self
+            .get(&key.group_id, &key.challenge_id)
+            .or_else(|| self.get(&group_key, &challenge_key));
+    }
+
+    fn get(&self, group: &str, challenge: &str) -> Option<&HashMap<String, PartValue>> {
+        return self.answers.get(group).and_then(|g| g.get(challenge));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::{challenge_config::ChallengeError, solution::Solution};
+
+    fn answers() -> Answers {
+        return serde_json::from_str(
+            r#"{"advent-of-code-2020": {"day-1-report-repair": {"Part 1": 514579, "Part 2": "abc"}},
+                "group": {"1": {"Part 1": 1}},
+                "my-group": {"1": {"Part 1": 2}, "day-2-product": {"Part 1": 3}}}"#,
+        )
+        .unwrap();
+    }
+
+    fn key(group_id: &str, challenge_id: &str, challenge_title: &str) -> ChallengeKey {
+        return ChallengeKey {
+            group_id: group_id.to_owned(),
+            group_name: "My Group".to_owned(),
+            challenge_id: challenge_id.to_owned(),
+            challenge_title: challenge_title.to_owned(),
+        };
+    }
+
+    #[rstest(
+        key,
+        expected_value,
+        case(key("group", "1", "Day 1: Sum"), Some(1)),
+        case(key("group", "2", "Day 2: Product"), Some(3)),
+        case(key("other", "1", "Day 1: Sum"), None),
+        case(key("group", "3", "Day 3"), None)
+    )]
+    fn expected(key: ChallengeKey, expected_value: Option<i128>) {
+        let answers = answers();
+        assert_eq!(
+            answers
+                .expected(&key)
+                .map(|e| e.get("Part 1").unwrap().clone()),
+            expected_value.map(PartValue::Integer)
+        );
+    }
+
+    #[rstest(
+        result,
+        expected_statuses,
+        expected_status,
+        case(
+            Ok(Solution::new().with_part("Part 1", 514579).with_part("Part 2", "abc")),
+            vec![PartStatus::Pass, PartStatus::Pass],
+            PartStatus::Pass
+        ),
+        case(
+            Ok(Solution::new().with_part("Part 1", 514579).with_part("Part 2", "abd")),
+            vec![PartStatus::Pass, PartStatus::Fail],
+            PartStatus::Fail
+        ),
+        case(
+            Ok(Solution::new().with_part("Part 1", 514579)),
+            vec![PartStatus::Pass, PartStatus::Fail],
+            PartStatus::Fail
+        ),
+        case(
+            Ok(Solution::new().with_part("Part 1", 514579).with_part("Part 2", "abc").with_part("Part 3", 1)),
+            vec![PartStatus::Pass, PartStatus::Pass, PartStatus::Missing],
+            PartStatus::Missing
+        ),
+        case(
+            Err(ChallengeError::new("Error").into()),
+            vec![PartStatus::Fail, PartStatus::Fail],
+            PartStatus::Fail
+        )
+    )]
+    fn verify(
+        result: Result<Solution>,
+        expected_statuses: Vec<PartStatus>,
+        expected_status: PartStatus,
+    ) {
+        let answers = answers();
+        let verification = ChallengeVerification::new(
+            "Advent of Code 2020",
+            "Day 1: Report Repair",
+            result,
+            answers.expected(&key(
+                "advent-of-code-2020",
+                "day-1-report-repair",
+                "Day 1: Report Repair",
+            )),
+        );
+        assert_eq!(
+            verification
+                .parts
+                .iter()
+                .map(|p| p.status)
+                .collect::<Vec<_>>(),
+            expected_statuses
+        );
+        assert_eq!(verification.status(), expected_status);
+    }
+}
@@ -0,0 +1 @@
+pub use challenges_types::groups::{Challenge, Group, GroupSummary, ItemName};
@@ -0,0 +1,242 @@
+use std::time::Instant;
+
+use anyhow::Result;
+pub use challenges_types::bench::{BenchReport, ChallengeBenchmark, PartTiming, Regression};
+
+use crate::{
+    groups::{
+        challenge_config::ChallengeConfig, group_config::GroupUnavailableError,
+        group_manager::GroupManager, solve_context::SolveContext,
+    },
+    input_store::{ChallengeKey, InputStore},
+};
+
+/// Solves each part of the challenge `runs` times and returns the timings of each part. Challenges
+/// solving their parts together are solved as a whole, timed as part 0. Each solve gets a context
+/// from `context`
+pub fn bench_challenge(
+    challenge: &dyn ChallengeConfig,
+    input: &str,
+    runs: usize,
+    context: &dyn Fn() -> SolveContext,
+) -> Result<Vec<PartTiming>> {
+    if challenge.solves_parts_together() {
+        let mut samples = vec![];
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            challenge.solve(input, &context())?;
+            samples.push(start.elapsed().as_secs_f64() * 1000.0);
+        }
+        return Ok(vec![PartTiming::from_samples(0, samples)]);
+    }
+    let mut timings = vec![];
+    for part in 1..=challenge.part_count() {
+        let mut samples = vec![];
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            challenge.solve_part(input, part, &context())?;
+            samples.push(start.elapsed().as_secs_f64() * 1000.0);
+        }
+        timings.push(PartTiming::from_samples(part, samples));
+    }
+    return Ok(timings);
+}
+
+/// Benchmarks every challenge that has a saved input, optionally limited to a single group.
+/// Each solve gets a context from `context`. Unavailable groups are skipped when benchmarking all
+/// the groups
+pub fn run(
+    manager: &GroupManager,
+    store: &InputStore,
+    runs: usize,
+    group_name: Option<&str>,
+    context: &dyn Fn() -> SolveContext,
+) -> Result<BenchReport, GroupUnavailableError> {
+    let mut challenges = vec![];
+    for group in manager.get_group_names() {
+        if group_name.is_some() && group_name != Some(group.as_str()) {
+            continue;
+        }
+        let group_config = manager.get_group(&group).unwrap();
+        let group_challenges = match group_config.challenges() {
+            Ok(c) => c,
+            Err(e) if group_name.is_some() => return Err(e),
+            Err(_) => continue,
+        };
+        for challenge in group_challenges {
+            let challenge_name = challenge.title().to_owned();
+            let key = ChallengeKey::new(group_config, challenge.as_ref());
+            let input = match store.load(&key) {
+                Ok(Some(i)) => i,
+                _ => continue,
+            };
+            let (parts, error) = match bench_challenge(challenge.as_ref(), &input, runs, context) {
+                Ok(parts) => (parts, None),
+                Err(e) => (vec![], Some(e.to_string())),
+            };
+            challenges.push(ChallengeBenchmark {
+                group: group.clone(),
+                challenge: challenge_name,
+                group_id: group_config.id(),
+                challenge_id: challenge.id(),
+                parts: parts,
+                error: error,
+            });
+        }
+    }
+    return Ok(BenchReport {
+        runs: runs.max(1),
+        challenges: challenges,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::{challenge_config::ChallengeError, solution::Solution};
+
+    struct TestChallenge {}
+
+    impl ChallengeConfig for TestChallenge {
+        fn title(&self) -> &str {
+            return "Test";
+        }
+
+        fn id(&self) -> String {
+            return "test".to_owned();
+        }
+
+        fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
+            context.check()?;
+            if input.is_empty() {
+                return Err(ChallengeError::new("Empty input").into());
+            }
+            return Ok(Solution::for_part(part, input));
+        }
+    }
+
+    /// Sleeps for a millisecond per solve, solving both parts at once
+    struct TogetherChallenge {}
+
+    impl ChallengeConfig for TogetherChallenge {
+        fn title(&self) -> &str {
+            return "Together";
+        }
+
+        fn id(&self) -> String {
+            return "together".to_owned();
+        }
+
+        fn solve_part(
+            &self,
+            _input: &str,
+            part: usize,
+            context: &SolveContext,
+        ) -> Result<Solution> {
+            return Ok(self.solve("", context)?.nth_part(part).unwrap());
+        }
+
+        fn solves_parts_together(&self) -> bool {
+            return true;
+        }
+
+        fn solve(&self, _input: &str, context: &SolveContext) -> Result<Solution> {
+            context.check()?;
+            thread::sleep(Duration::from_millis(1));
+            return Ok(Solution::new()
+                .with_part("Part 1", 1)
+                .with_part("Part 2", 2));
+        }
+    }
+
+    fn report(challenge: &str, challenge_id: &str, median_ms: f64) -> BenchReport {
+        return BenchReport {
+            runs: 1,
+            challenges: vec![ChallengeBenchmark {
+                group: "Group".to_owned(),
+                challenge: challenge.to_owned(),
+                group_id: "group".to_owned(),
+                challenge_id: challenge_id.to_owned(),
+                parts: vec![PartTiming {
+                    part: 1,
+                    min_ms: median_ms,
+                    median_ms: median_ms,
+                    max_ms: median_ms,
+                }],
+                error: None,
+            }],
+        };
+    }
+
+    #[rstest(
+        samples,
+        expected,
+        case(vec![3.0, 1.0, 2.0], (1.0, 2.0, 3.0)),
+        case(vec![4.0, 1.0, 2.0, 3.0], (1.0, 2.5, 4.0)),
+        case(vec![5.0], (5.0, 5.0, 5.0))
+    )]
+    fn from_samples(samples: Vec<f64>, expected: (f64, f64, f64)) {
+        let timing = PartTiming::from_samples(1, samples);
+        assert_eq!((timing.min_ms, timing.median_ms, timing.max_ms), expected);
+    }
+
+    #[test]
+    fn bench_challenge_parts() {
+        let context = SolveContext::default;
+        let timings = bench_challenge(&TestChallenge {}, "input", 3, &context).unwrap();
+        assert_eq!(
+            timings.iter().map(|t| t.part).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(bench_challenge(&TestChallenge {}, "", 3, &context).is_err());
+
+        let cancelled = || {
+            let context = SolveContext::default();
+            context.token().cancel();
+            return context;
+        };
+        assert!(bench_challenge(&TestChallenge {}, "input", 3, &cancelled).is_err());
+    }
+
+    #[test]
+    fn bench_challenge_together() {
+        let timings =
+            bench_challenge(&TogetherChallenge {}, "input", 3, &SolveContext::default).unwrap();
+        assert_eq!(timings.len(), 1);
+        assert_eq!(timings[0].part, 0);
+        assert!(timings[0].min_ms >= 1.0);
+    }
+
+    #[rstest(
+        current,
+        baseline,
+        threshold,
+        expected_count,
+        case(10.0, 10.0, 0.2, 0),
+        case(11.0, 10.0, 0.2, 0),
+        case(13.0, 10.0, 0.2, 1),
+        case(5.0, 10.0, 0.2, 0)
+    )]
+    fn compare(current: f64, baseline: f64, threshold: f64, expected_count: usize) {
+        let regressions = report("Challenge", "challenge", current)
+            .compare(&report("Challenge", "challenge", baseline), threshold);
+        assert_eq!(regressions.len(), expected_count);
+    }
+
+    #[rstest(
+        baseline,
+        expected_count,
+        case(report("Renamed", "challenge", 10.0), 1),
+        case(report("Challenge", "other", 10.0), 0),
+        case(report("Challenge", "", 10.0), 1),
+        case(report("Other", "", 10.0), 0)
+    )]
+    fn compare_matches_ids(baseline: BenchReport, expected_count: usize) {
+        let regressions = report("Challenge", "challenge", 20.0).compare(&baseline, 0.2);
+        assert_eq!(regressions.len(), expected_count);
+    }
+}
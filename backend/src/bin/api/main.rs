@@ -1,200 +1,651 @@
-#![feature(proc_macro_hygiene, decl_macro)]
+use std::{panic, process, sync::Arc, time::Duration};
 
-#[macro_use]
-extern crate cached;
-
-use anyhow::Result;
-use challenges::groups::group_manager::GroupManager;
-use regex::Regex;
-use rocket::{http::Method, Config, Data};
-use rocket_contrib::json::Json;
+use anyhow::{bail, Result};
+use challenges::{
+    answers::{Answers, ChallengeVerification},
+    api::{Challenge, Group, GroupSummary, ItemName},
+    bench::BenchReport,
+    config::Config,
+    groups::{
+        cancellation::CancellationToken,
+        challenge_config::{ChallengeConfig, ChallengeError, ChallengeErrorKind},
+        group_config::{GroupConfig, GroupUnavailableError},
+        group_manager::GroupManager,
+        search::SearchResult,
+        solve_context::SolveContext,
+    },
+    input_store::{ChallengeKey, InputStore},
+    jobs::{Job, JobManager},
+    utils::create_key,
+};
+use rocket::{
+    data::{ByteUnit, Data},
+    http::{uri::Origin, Method, Status},
+    response::{
+        status::Custom,
+        stream::{Event, EventStream},
+        Redirect,
+    },
+    serde::json::Json,
+    tokio::{task, time},
+    State,
+};
 use rocket_cors::{AllowedHeaders, AllowedOrigins};
-use serde::Serialize;
+use utoipa::{IntoParams, OpenApi};
 
 #[macro_use]
 extern crate rocket;
 
 const DEFAULT_PORT: u16 = 8081;
+/// Interval at which job events check for changes
+const EVENTS_INTERVAL: Duration = Duration::from_millis(200);
+/// Largest input read from a request body
+const INPUT_LIMIT: ByteUnit = ByteUnit::Mebibyte(16);
+/// Most runs of a benchmark
+const MAX_BENCH_RUNS: usize = 100;
 
-#[derive(Clone, Serialize, Debug)]
-pub struct ItemName {
-    key: String,
-    display_name: String,
-}
-
-#[derive(Clone, Serialize, Debug)]
-pub struct Group {
-    name: String,
-    url: String,
-    challenges: Vec<ItemName>,
-}
+/// Groups shared by all the requests, so that their listings stay cached between them
+type Groups = State<Arc<GroupManager>>;
 
-fn create_key(display_name: &str) -> String {
-    let re = Regex::new(r"[^A-Za-z0-9]").unwrap();
-    return re
-        .replace_all(display_name, "-")
-        .to_lowercase()
-        .replace("--", "-");
+/// Runs a call that may block, like listing a remote group or solving, on the blocking thread
+/// pool so that it doesn't hold up the other requests
+async fn blocking<T, F>(call: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    return match task::spawn_blocking(call).await {
+        Ok(value) => value,
+        // Rocket answers the requests whose handler panicked with a 500
+        Err(e) => panic::resume_unwind(e.into_panic()),
+    };
 }
 
-cached! {
-    GROUPS;
-    fn get_groups() -> Vec<ItemName> = {
-        let manager = GroupManager::new();
-        let value: Vec<ItemName> = manager
+fn get_groups(manager: &GroupManager) -> Vec<ItemName> {
+    return manager
         .get_group_names()
-        .iter()
+        .into_iter()
         .map(|g| ItemName {
-            key: create_key(g),
-            display_name: (*g).clone(),
+            key: manager.get_group(&g).unwrap().id(),
+            display_name: g,
         })
         .collect();
-        return value;
+}
+
+/// Group whose id is the key, or else whose key in the first versions of the API (`create_key`
+/// of its name) is
+fn find_group<'a>(manager: &'a GroupManager, key: &str) -> Option<&'a dyn GroupConfig> {
+    if let Some(group) = manager.get_group_by_id(key) {
+        return Some(group);
     }
+    return manager
+        .get_group_names()
+        .iter()
+        .find(|n| create_key(n) == key)
+        .and_then(|n| manager.get_group(n));
 }
 
-cached! {
-    GROUP_NAME;
-    fn get_group_name(key: String) -> Option<ItemName> = {
-        let groups = get_groups();
-        return match groups.iter().find(|g| g.key == key) {
-            Some(s) => Some(s.clone()),
-            None => None,
-        };
+/// Challenge of the group found like `find_group`
+fn find_group_challenge(
+    group: &dyn GroupConfig,
+    key: &str,
+) -> Result<Option<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+    let mut challenges = group.challenges()?;
+    let found = challenges
+        .iter()
+        .position(|c| c.id() == key)
+        .or_else(|| challenges.iter().position(|c| create_key(c.title()) == key));
+    return Ok(found.map(|i| challenges.swap_remove(i)));
+}
+
+/// Challenge named by the keys of a request, along with the key of its saved input
+struct Located {
+    key: ChallengeKey,
+    challenge: Box<dyn ChallengeConfig>,
+}
+
+/// Finds the challenge of a request to `uri`. Requests made with legacy keys fail with a redirect
+/// to the same path with the ids
+fn locate(
+    manager: &GroupManager,
+    uri: &str,
+    group_key: &str,
+    challenge_key: &str,
+) -> Result<Option<Located>, ApiError> {
+    let group = match find_group(manager, group_key) {
+        Some(g) => g,
+        None => return Ok(None),
+    };
+    let challenge = match find_group_challenge(group, challenge_key)? {
+        Some(c) => c,
+        None => return Ok(None),
+    };
+    if group.id() != group_key || challenge.id() != challenge_key {
+        return Err(moved(uri, &[group.id(), challenge.id()]));
     }
+    return Ok(Some(Located {
+        key: ChallengeKey::new(group, challenge.as_ref()),
+        challenge: challenge,
+    }));
 }
 
-cached! {
-    GROUP;
-    fn get_group(group_key: String) -> Option<Group> = {
-        // Get the group
-        let group_name = match get_group_name(group_key) {
-            Some(g) => g,
-            None => return None,
-        };
-        let manager = GroupManager::new();
-        let group = match manager.get_group(&group_name.display_name) {
-            Some(g) => g,
-            None => return None,
-        };
-        let challenges: Vec<ItemName> = group
-            .challenge_names()
-            .iter()
-            .map(|c| ItemName {
-                key: create_key(c),
-                display_name: (*c).clone(),
-            })
-            .collect();
-        return Some(Group {
-            name: group.name().to_owned(),
-            url: group.url().to_owned(),
-            challenges: challenges,
-        });
+/// Permanent redirect of a request to the same path with the ids in place of the keys following
+/// `groups`, keeping the query. Unlike a 301, the 308 keeps the method and the body of solves
+fn moved(uri: &str, ids: &[String]) -> ApiError {
+    let (path, query) = match uri.split_once('?') {
+        Some((p, q)) => (p, Some(q)),
+        None => (uri, None),
+    };
+    let mut segments: Vec<&str> = path.split('/').collect();
+    let start = segments
+        .iter()
+        .position(|s| *s == "groups")
+        .map_or(segments.len(), |i| i + 1);
+    for (segment, id) in segments.iter_mut().skip(start).zip(ids) {
+        *segment = id;
     }
+    let mut location = segments.join("/");
+    if let Some(query) = query {
+        location = format!("{}?{}", location, query);
+    }
+    return ApiError::Moved(Box::new(Redirect::permanent(location)));
 }
 
-cached! {
-    CHALLENGE_NAME;
-    fn get_challenge_name(group_key: String, key: String) -> Option<ItemName> = {
-        let group = match get_group(group_key) {
-            Some(g) => g,
-            None => return None,
-        };
-        return match group.challenges.iter().find(|g| g.key == key) {
-            Some(s) => Some(s.clone()),
-            None => None,
+/// Challenges of the group as currently listed. The groups keep their own listing, remote
+/// groups until their TTL expires, and `POST /refresh` lists all of them again
+fn describe_group(group: &dyn GroupConfig) -> Result<Group, GroupUnavailableError> {
+    let challenges: Vec<ItemName> = group
+        .challenges()?
+        .into_iter()
+        .map(|c| ItemName {
+            key: c.id(),
+            display_name: c.title().to_owned(),
+        })
+        .collect();
+    return Ok(Group {
+        name: group.name().to_owned(),
+        url: group.url().to_owned(),
+        challenges: challenges,
+    });
+}
+
+/// Error response, unavailable groups are reported as a 502 carrying the group and the error.
+/// Requests made with legacy keys are redirected rather than answered
+#[derive(Responder)]
+enum ApiError {
+    Unavailable(Custom<Json<GroupUnavailableError>>),
+    Failed(Custom<String>),
+    Moved(Box<Redirect>),
+}
+
+impl From<GroupUnavailableError> for ApiError {
+    fn from(err: GroupUnavailableError) -> Self {
+        return ApiError::Unavailable(Custom(Status::BadGateway, Json(err)));
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        return match err.downcast::<GroupUnavailableError>() {
+            Ok(e) => e.into(),
+            Err(err) => ApiError::Failed(solve_error(err)),
         };
     }
 }
 
+fn get_group_summaries(manager: &GroupManager) -> Vec<GroupSummary> {
+    return get_groups(manager)
+        .into_iter()
+        .map(|g| GroupSummary {
+            health: manager.get_group_health(&g.display_name).unwrap(),
+            key: g.key,
+            display_name: g.display_name,
+        })
+        .collect();
+}
+
+/// Groups along with their health
+#[utoipa::path(responses((status = 200, body = Vec<GroupSummary>)))]
 #[get("/groups")]
-fn groups() -> Json<Vec<ItemName>> {
-    Json(get_groups())
+async fn groups(groups: &Groups) -> Json<Vec<GroupSummary>> {
+    let manager = groups.inner().clone();
+    return Json(blocking(move || get_group_summaries(&manager)).await);
 }
 
-#[get("/groups/<group_key>")]
-fn group(group_key: String) -> Json<Option<Group>> {
-    return Json(get_group(group_key));
+/// Lists the challenges of every group again, e.g. after a service was restarted with new
+/// challenges, and responds with the groups like `GET /groups`
+#[utoipa::path(responses((status = 200, body = Vec<GroupSummary>)))]
+#[post("/refresh")]
+async fn refresh(groups: &Groups) -> Json<Vec<GroupSummary>> {
+    let manager = groups.inner().clone();
+    return Json(
+        blocking(move || {
+            manager.refresh();
+            return get_group_summaries(&manager);
+        })
+        .await,
+    );
 }
 
-#[derive(Serialize, Debug)]
-pub struct Challenge {
-    title: String,
-    description: String,
+/// Challenges of the group
+#[utoipa::path(responses(
+        (status = 200, body = Group),
+        (status = 404, description = "Unknown group"),
+        (status = 308, description = "Keys of the first versions of the API, redirected to the ids"),
+        (status = 502, description = "The group is unavailable", body = GroupUnavailableError),
+))]
+#[get("/groups/<group_key>")]
+async fn group(
+    groups: &Groups,
+    origin: &Origin<'_>,
+    group_key: String,
+) -> Result<Option<Json<Group>>, ApiError> {
+    let manager = groups.inner().clone();
+    let uri = origin.to_string();
+    return blocking(move || {
+        let group = match find_group(&manager, &group_key) {
+            Some(g) => g,
+            None => return Ok(None),
+        };
+        if group.id() != group_key {
+            return Err(moved(&uri, &[group.id()]));
+        }
+        return Ok(Some(Json(describe_group(group)?)));
+    })
+    .await;
 }
 
+#[utoipa::path(responses(
+        (status = 200, body = Challenge),
+        (status = 404, description = "Unknown group or challenge"),
+        (status = 308, description = "Keys of the first versions of the API, redirected to the ids"),
+        (status = 502, description = "The group is unavailable", body = GroupUnavailableError),
+))]
 #[get("/groups/<group_key>/<challenge_key>")]
-fn challenge(group_key: String, challenge_key: String) -> Option<Json<Challenge>> {
-    // Get the group
-    let group_name = match get_group_name(group_key.clone()) {
-        Some(g) => g,
-        None => return None,
-    };
-    // Get the challenge
-    let challenge_name = match get_challenge_name(group_key.clone(), challenge_key) {
-        Some(g) => g,
-        None => return None,
-    };
-    let manager = GroupManager::new();
-    let group = match manager.get_group(&group_name.display_name) {
-        Some(g) => g,
-        None => return None,
-    };
-    let challenge = match group.challenge(&challenge_name.display_name) {
-        Some(c) => c,
-        None => return None,
+async fn challenge(
+    groups: &Groups,
+    origin: &Origin<'_>,
+    group_key: String,
+    challenge_key: String,
+) -> Result<Option<Json<Challenge>>, ApiError> {
+    let manager = groups.inner().clone();
+    let uri = origin.to_string();
+    return blocking(move || find_challenge(&manager, &uri, &group_key, &challenge_key)).await;
+}
+
+fn find_challenge(
+    manager: &GroupManager,
+    uri: &str,
+    group_key: &str,
+    challenge_key: &str,
+) -> Result<Option<Json<Challenge>>, ApiError> {
+    let challenge = match locate(manager, uri, group_key, challenge_key)? {
+        Some(l) => l.challenge,
+        None => return Ok(None),
     };
 
-    Some(Json(Challenge {
+    return Ok(Some(Json(Challenge {
         title: challenge.title().to_owned(),
         description: challenge.description().to_owned(),
-    }))
+    })));
+}
+
+/// Responds with 408 when the solve timed out, 500 otherwise
+fn solve_error(err: anyhow::Error) -> Custom<String> {
+    let status = match err
+        .downcast_ref::<ChallengeError>()
+        .map(ChallengeError::kind)
+    {
+        Some(ChallengeErrorKind::TimedOut) => Status::RequestTimeout,
+        _ => Status::InternalServerError,
+    };
+    return Custom(status, err.to_string());
+}
+
+/// Query of the solves and jobs
+#[derive(FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct SolveOptions {
+    /// Part to solve, all of them when absent
+    part: Option<usize>,
+    /// Solves the saved input rather than the request body
+    use_saved: Option<bool>,
+}
+
+/// Reads the request body, or nothing when the saved input is used instead
+async fn read_data(use_saved: Option<bool>, input: Data<'_>) -> Result<Option<String>> {
+    if use_saved.unwrap_or(false) {
+        return Ok(None);
+    }
+    let data = input.open(INPUT_LIMIT).into_string().await?;
+    if !data.is_complete() {
+        bail!("The input is larger than {}", INPUT_LIMIT);
+    }
+    return Ok(Some(data.into_inner()));
+}
+
+/// Finds the challenge along with the input read from the request body, or its saved input
+fn get_challenge_input(
+    manager: &GroupManager,
+    uri: &str,
+    group_key: &str,
+    challenge_key: &str,
+    input: Option<String>,
+) -> Option<Result<(Located, String), ApiError>> {
+    let located = match locate(manager, uri, group_key, challenge_key) {
+        Ok(Some(l)) => l,
+        Ok(None) => return None,
+        Err(err) => return Some(Err(err)),
+    };
+
+    let data = match input {
+        Some(d) => d,
+        None => match InputStore::from_env().load(&located.key) {
+            Ok(Some(d)) => d,
+            Ok(None) => return None,
+            Err(err) => return Some(Err(err.into())),
+        },
+    };
+    return Some(Ok((located, data)));
 }
 
+/// Queues the solve on the job workers and responds with the queued job, whose status and
+/// result are then polled through `/jobs/<id>`
+#[utoipa::path(
+    params(SolveOptions),
+    request_body(content = String, content_type = "text/plain", description = "Puzzle input"),
+    responses(
+        (status = 200, body = Job),
+        (status = 404, description = "Unknown group or challenge, or no saved input"),
+        (status = 500, description = "The input can't be read", body = String, content_type = "text/plain"),
+        (status = 308, description = "Keys of the first versions of the API, redirected to the ids"),
+        (status = 502, description = "The group is unavailable", body = GroupUnavailableError),
+    )
+)]
 #[post(
-    "/groups/<group_key>/<challenge_key>/solve",
+    "/groups/<group_key>/<challenge_key>/solve?<options..>",
     format = "text/plain",
     data = "<input>"
 )]
-fn solve(group_key: String, challenge_key: String, input: Data) -> Option<Result<String>> {
-    // Get the group
-    let group_name = match get_group_name(group_key.clone()) {
-        Some(g) => g,
-        None => return None,
+async fn solve(
+    groups: &Groups,
+    jobs: &State<JobManager>,
+    origin: &Origin<'_>,
+    group_key: String,
+    challenge_key: String,
+    options: SolveOptions,
+    input: Data<'_>,
+) -> Option<Result<Json<Job>, ApiError>> {
+    let input = match read_data(options.use_saved, input).await {
+        Ok(i) => i,
+        Err(err) => return Some(Err(err.into())),
     };
-    // Get the challenge
-    let challenge_name = match get_challenge_name(group_key.clone(), challenge_key) {
-        Some(g) => g,
-        None => return None,
+    let manager = groups.inner().clone();
+    let uri = origin.to_string();
+    let found = blocking(move || {
+        return get_challenge_input(&manager, &uri, &group_key, &challenge_key, input);
+    })
+    .await;
+    let (located, data) = match found? {
+        Ok(c) => c,
+        Err(err) => return Some(Err(err)),
     };
-    let manager = GroupManager::new();
-    let group = match manager.get_group(&group_name.display_name) {
-        Some(g) => g,
-        None => return None,
+    return Some(
+        jobs.submit(located.challenge, data, options.part)
+            .map(Json)
+            .map_err(ApiError::from),
+    );
+}
+
+#[utoipa::path(responses(
+    (status = 200, body = Job),
+    (status = 404, description = "Unknown job")
+))]
+#[get("/jobs/<id>")]
+fn job(jobs: &State<JobManager>, id: u64) -> Option<Json<Job>> {
+    return jobs.get(id).map(Json);
+}
+
+/// Server-sent events carrying the job each time its status or progress changes, the stream ends
+/// with the finished job
+#[utoipa::path(responses(
+    (status = 200, body = Job, content_type = "text/event-stream"),
+    (status = 404, description = "Unknown job")
+))]
+#[get("/jobs/<id>/events")]
+fn job_events(jobs: &State<JobManager>, id: u64) -> Option<EventStream![Event + '_]> {
+    let mut job = jobs.get(id)?;
+    return Some(EventStream! {
+        let mut last: Option<Job> = None;
+        loop {
+            if last.as_ref() != Some(&job) {
+                yield Event::json(&job);
+                if job.status.is_finished() {
+                    break;
+                }
+                last = Some(job.clone());
+            }
+            time::sleep(EVENTS_INTERVAL).await;
+            job = match jobs.get(id) {
+                Some(j) => j,
+                None => break,
+            };
+        }
+    });
+}
+
+/// Cancels a queued or running job, finished jobs are left untouched
+#[utoipa::path(responses(
+    (status = 200, body = Job),
+    (status = 404, description = "Unknown job")
+))]
+#[delete("/jobs/<id>")]
+fn cancel_job(jobs: &State<JobManager>, id: u64) -> Option<Json<Job>> {
+    return jobs.cancel(id).map(Json);
+}
+
+/// Saves the puzzle input of the challenge, solved with `use_saved=true`
+#[utoipa::path(
+    request_body(content = String, content_type = "text/plain", description = "Puzzle input"),
+    responses(
+        (status = 200, description = "The input was saved"),
+        (status = 404, description = "Unknown group or challenge"),
+        (status = 500, description = "The input can't be read or saved", body = String, content_type = "text/plain"),
+        (status = 308, description = "Keys of the first versions of the API, redirected to the ids"),
+        (status = 502, description = "The group is unavailable", body = GroupUnavailableError),
+    )
+)]
+#[put(
+    "/groups/<group_key>/<challenge_key>/input",
+    format = "text/plain",
+    data = "<input>"
+)]
+async fn save_input(
+    groups: &Groups,
+    origin: &Origin<'_>,
+    group_key: String,
+    challenge_key: String,
+    input: Data<'_>,
+) -> Option<Result<(), ApiError>> {
+    let data = match read_data(None, input).await {
+        Ok(d) => d.unwrap_or_default(),
+        Err(err) => return Some(Err(err.into())),
     };
-    let challenge = match group.challenge(&challenge_name.display_name) {
-        Some(c) => c,
-        None => return None,
+    let manager = groups.inner().clone();
+    let uri = origin.to_string();
+    return blocking(move || store_input(&manager, &uri, &group_key, &challenge_key, data)).await;
+}
+
+fn store_input(
+    manager: &GroupManager,
+    uri: &str,
+    group_key: &str,
+    challenge_key: &str,
+    data: String,
+) -> Option<Result<(), ApiError>> {
+    let located = match locate(manager, uri, group_key, challenge_key) {
+        Ok(Some(l)) => l,
+        Ok(None) => return None,
+        Err(err) => return Some(Err(err)),
     };
+    return Some(
+        InputStore::from_env()
+            .save(&located.key, &data)
+            .map_err(ApiError::from),
+    );
+}
 
-    let mut data_bytes: Vec<u8> = vec![];
-    match input.stream_to(&mut data_bytes) {
-        Err(err) => return Some(Err(err.into())),
-        _ => {}
+/// Solves the saved inputs `runs` times, at most 100, optionally those of a single group. Each
+/// solve fails once it takes longer than the time limit of the jobs
+#[utoipa::path(responses(
+    (status = 200, body = BenchReport),
+    (status = 400, description = "More than 100 runs", body = String, content_type = "text/plain"),
+    (status = 404, description = "Unknown group"),
+    (status = 502, description = "The group is unavailable", body = GroupUnavailableError)
+))]
+#[get("/bench?<runs>&<group>")]
+async fn bench(
+    groups: &Groups,
+    jobs: &State<JobManager>,
+    runs: Option<usize>,
+    group: Option<String>,
+) -> Option<Result<Json<BenchReport>, ApiError>> {
+    let runs = runs.unwrap_or(1);
+    if runs > MAX_BENCH_RUNS {
+        let message = format!("At most {} runs are allowed", MAX_BENCH_RUNS);
+        return Some(Err(ApiError::Failed(Custom(Status::BadRequest, message))));
+    }
+    let manager = groups.inner().clone();
+    let timeout = jobs.timeout();
+    return blocking(move || run_bench(&manager, runs, group, timeout)).await;
+}
+
+fn run_bench(
+    manager: &GroupManager,
+    runs: usize,
+    group: Option<String>,
+    timeout: Duration,
+) -> Option<Result<Json<BenchReport>, ApiError>> {
+    let group_name = match group {
+        Some(key) => match find_group(manager, &key) {
+            Some(g) => Some(g.name().to_owned()),
+            None => return None,
+        },
+        None => None,
     };
-    return match String::from_utf8(data_bytes) {
-        Ok(data) => Some(challenge.solve(&data)),
-        Err(err) => Some(Err(err.into())),
+    return Some(
+        challenges::bench::run(
+            manager,
+            &InputStore::from_env(),
+            runs,
+            group_name.as_deref(),
+            &|| SolveContext::new(CancellationToken::with_timeout(timeout)),
+        )
+        .map(Json)
+        .map_err(ApiError::from),
+    );
+}
+
+/// Solves the saved inputs, optionally those of a single group, and compares the results with the
+/// known answers. Each solve fails once it takes longer than the time limit of the jobs
+#[utoipa::path(responses(
+    (status = 200, body = Vec<ChallengeVerification>),
+    (status = 404, description = "Unknown group"),
+    (status = 502, description = "The group is unavailable", body = GroupUnavailableError)
+))]
+#[get("/verify?<group>")]
+async fn verify(
+    groups: &Groups,
+    jobs: &State<JobManager>,
+    group: Option<String>,
+) -> Option<Result<Json<Vec<ChallengeVerification>>, ApiError>> {
+    let manager = groups.inner().clone();
+    let timeout = jobs.timeout();
+    return blocking(move || verify_group(&manager, group, timeout)).await;
+}
+
+fn verify_group(
+    manager: &GroupManager,
+    group: Option<String>,
+    timeout: Duration,
+) -> Option<Result<Json<Vec<ChallengeVerification>>, ApiError>> {
+    let group_name = match group {
+        Some(key) => match find_group(manager, &key) {
+            Some(g) => Some(g.name().to_owned()),
+            None => return None,
+        },
+        None => None,
     };
+    let store = InputStore::from_env();
+    let answers = match Answers::from_env(&store) {
+        Ok(a) => a,
+        Err(err) => return Some(Err(err.into())),
+    };
+    return Some(
+        manager
+            .verify(&store, &answers, group_name.as_deref(), &|| {
+                SolveContext::new(CancellationToken::with_timeout(timeout))
+            })
+            .map(Json)
+            .map_err(ApiError::from),
+    );
+}
+
+/// Challenges whose title, description or tags contain every term of `q`
+#[utoipa::path(responses((status = 200, body = Vec<SearchResult>)))]
+#[get("/search?<q>")]
+async fn search(groups: &Groups, q: String) -> Json<Vec<SearchResult>> {
+    let manager = groups.inner().clone();
+    return Json(blocking(move || manager.search(&q)).await);
 }
 
-fn main() {
+/// OpenAPI document of the routes, generated from their definitions
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Challenges API",
+        description = "Lists and solves the challenges of the groups served by the backend"
+    ),
+    servers((url = "/api")),
+    paths(
+        groups,
+        refresh,
+        group,
+        challenge,
+        solve,
+        job,
+        job_events,
+        cancel_job,
+        save_input,
+        bench,
+        verify,
+        search,
+        openapi_document
+    )
+)]
+struct ApiDoc;
+
+/// OpenAPI 3 document of the API, from which clients can be generated
+#[utoipa::path(responses((status = 200, description = "The OpenAPI document")))]
+#[get("/openapi.json")]
+fn openapi_document() -> Json<utoipa::openapi::OpenApi> {
+    return Json(ApiDoc::openapi());
+}
+
+#[rocket::main]
+async fn main() {
+    // Report config errors before launching
+    let config = Config::from_env().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+
     let allowed_origins = AllowedOrigins::all();
 
     // You can also deserialize this
     let cors = rocket_cors::CorsOptions {
         allowed_origins,
-        allowed_methods: vec![Method::Get, Method::Post]
+        allowed_methods: vec![Method::Get, Method::Post, Method::Put, Method::Delete]
             .into_iter()
             .map(From::from)
             .collect(),
@@ -208,10 +659,33 @@ fn main() {
         Ok(p) => p.parse::<u16>().unwrap_or(DEFAULT_PORT),
         Err(_) => DEFAULT_PORT,
     };
-    let mut config = Config::active().unwrap();
-    config.set_port(port);
-    rocket::custom(config)
-        .mount("/api/", routes![groups, group, challenge, solve])
+    let figment = rocket::Config::figment().merge(("port", port));
+    let result = rocket::custom(figment)
+        .mount(
+            "/api/",
+            routes![
+                groups,
+                group,
+                challenge,
+                solve,
+                job,
+                job_events,
+                cancel_job,
+                save_input,
+                bench,
+                verify,
+                search,
+                refresh,
+                openapi_document
+            ],
+        )
+        .manage(Arc::new(GroupManager::from_config(&config)))
+        .manage(JobManager::from_env())
         .attach(cors)
-        .launch();
+        .launch()
+        .await;
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,388 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+    time::Duration,
+};
+
+use challenges::{
+    answers::{Answers, ChallengeVerification, PartStatus},
+    bench::{self, BenchReport},
+    config::Config,
+    groups::{
+        cancellation::CancellationToken,
+        challenge_config::{ChallengeConfig, ChallengeError, ChallengeErrorKind},
+        group_manager::GroupManager,
+        solve_context::SolveContext,
+    },
+    input_store::InputStore,
+    utils::create_key,
+};
+use clap::{Parser, Subcommand};
+
+/// Exit code used when the challenge could not be solved
+const SOLVE_ERROR_CODE: i32 = 1;
+/// Exit code used for invalid arguments, unknown groups or challenges and IO errors
+const USAGE_ERROR_CODE: i32 = 2;
+/// Exit code used when a benchmark is slower than its baseline
+const REGRESSION_ERROR_CODE: i32 = 3;
+/// Exit code used when a challenge doesn't return its known answer
+const VERIFY_ERROR_CODE: i32 = 4;
+/// Exit code used when the solve didn't finish before the timeout
+const TIMEOUT_ERROR_CODE: i32 = 5;
+/// Exit code used when the challenges of a group can't be listed
+const UNAVAILABLE_ERROR_CODE: i32 = 6;
+
+#[derive(Parser)]
+#[command(name = "challenges-cli", about = "Lists and solves challenges")]
+struct Cli {
+    /// Print the output as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// TOML file listing the groups. Defaults to $CHALLENGES_CONFIG or ./challenges.toml if it exists
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the available groups
+    Groups,
+    /// List the challenges of a group
+    Challenges {
+        /// Name or id of the group
+        group: String,
+    },
+    /// Solve a challenge
+    Solve {
+        /// Name or id of the group
+        group: String,
+        /// Name or id of the challenge
+        challenge: String,
+        /// File containing the input. Reads from stdin when omitted or "-"
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Only solve the given part
+        #[arg(short, long)]
+        part: Option<usize>,
+        /// Stop solving after the given number of seconds
+        #[arg(short, long, value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    /// Benchmark the challenges that have a stored input
+    Bench {
+        /// Directory containing the inputs, as `<group key>/<challenge key>.txt`. Defaults to $CHALLENGES_INPUTS_DIR or ./inputs
+        #[arg(long)]
+        inputs: Option<PathBuf>,
+        /// Number of times each part is solved
+        #[arg(short, long, default_value_t = 5)]
+        runs: usize,
+        /// Only benchmark the given group (name or id)
+        #[arg(short, long)]
+        group: Option<String>,
+        /// Write the report to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Compare the report against a previously written report
+        #[arg(short, long)]
+        baseline: Option<PathBuf>,
+        /// Slowdown ratio of the median over which a part is flagged as a regression
+        #[arg(long, default_value_t = 0.2)]
+        threshold: f64,
+    },
+    /// Solve the challenges that have a saved input and compare the results with the known answers
+    Verify {
+        /// Directory containing the inputs, as `<group key>/<challenge key>.txt`. Defaults to $CHALLENGES_INPUTS_DIR or ./inputs
+        #[arg(long)]
+        inputs: Option<PathBuf>,
+        /// JSON file containing the known answers. Defaults to $CHALLENGES_ANSWERS_FILE or answers.json in the inputs directory
+        #[arg(short, long)]
+        answers: Option<PathBuf>,
+        /// Only verify the given group (name or id)
+        #[arg(short, long)]
+        group: Option<String>,
+    },
+}
+
+/// Parses a positive number of seconds
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let seconds = value.parse::<f64>().map_err(|e| e.to_string())?;
+    if seconds.is_nan() || seconds <= 0.0 {
+        return Err("the timeout must be greater than 0".to_owned());
+    }
+    return Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string());
+}
+
+fn exit_with_error(message: &str, code: i32) -> ! {
+    eprintln!("{}", message);
+    process::exit(code);
+}
+
+/// Whether the query is the display name of the item, its id, or its key in the first versions
+/// of the API
+fn designates(query: &str, name: &str, id: &str) -> bool {
+    return query == name || query == id || query == create_key(name);
+}
+
+/// Finds a group by its display name or its id
+fn find_group_name(manager: &GroupManager, name_or_id: &str) -> Option<String> {
+    return manager
+        .get_group_names()
+        .into_iter()
+        .find(|n| designates(name_or_id, n, &manager.get_group(n).unwrap().id()));
+}
+
+fn print_names(names: Vec<String>, json: bool) {
+    if json {
+        println!("{}", serde_json::to_string(&names).unwrap());
+        return;
+    }
+    for name in names {
+        println!("{}", name);
+    }
+}
+
+fn find_challenge(
+    manager: &GroupManager,
+    group: &str,
+    challenge: &str,
+) -> Box<dyn ChallengeConfig> {
+    let group_name = find_group_name(manager, group)
+        .unwrap_or_else(|| exit_with_error(&format!("Unknown group: {}", group), USAGE_ERROR_CODE));
+    return manager
+        .get_group(&group_name)
+        .unwrap()
+        .challenges()
+        .unwrap_or_else(|e| exit_with_error(&e.to_string(), UNAVAILABLE_ERROR_CODE))
+        .into_iter()
+        .find(|c| designates(challenge, c.title(), &c.id()))
+        .unwrap_or_else(|| {
+            exit_with_error(
+                &format!("Unknown challenge: {}", challenge),
+                USAGE_ERROR_CODE,
+            )
+        });
+}
+
+/// Challenge names of an existing group, exits if the group is unavailable
+fn challenge_names(manager: &GroupManager, group_name: &str) -> Vec<String> {
+    return manager
+        .get_group_challenge_names(group_name)
+        .unwrap_or_else(|e| exit_with_error(&e.to_string(), UNAVAILABLE_ERROR_CODE))
+        .unwrap();
+}
+
+fn read_input(input: Option<String>) -> io::Result<String> {
+    return match input.as_deref() {
+        None | Some("-") => {
+            let mut buffer = String::new();
+            io::stdin().read_to_string(&mut buffer)?;
+            Ok(buffer)
+        }
+        Some(path) => fs::read_to_string(path),
+    };
+}
+
+fn print_report(report: &BenchReport) {
+    for challenge in &report.challenges {
+        println!("{} - {}", challenge.group, challenge.challenge);
+        if let Some(error) = &challenge.error {
+            println!("  Error: {}", error);
+        }
+        for part in &challenge.parts {
+            println!(
+                "  {}: min {:.3}ms, median {:.3}ms, max {:.3}ms",
+                part_label(part.part),
+                part.min_ms,
+                part.median_ms,
+                part.max_ms
+            );
+        }
+    }
+}
+
+/// Part 0 times the whole solve of the challenges solving their parts together
+fn part_label(part: usize) -> String {
+    return match part {
+        0 => "All parts".to_owned(),
+        p => format!("Part {}", p),
+    };
+}
+
+fn print_verifications(verifications: &Vec<ChallengeVerification>) {
+    for verification in verifications {
+        println!(
+            "{:?} {} - {}",
+            verification.status(),
+            verification.group,
+            verification.challenge
+        );
+        if let Some(error) = &verification.error {
+            println!("  Error: {}", error);
+        }
+        for part in &verification.parts {
+            let actual = part
+                .actual
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or("nothing".to_owned());
+            match &part.expected {
+                Some(expected) if part.status == PartStatus::Fail => println!(
+                    "  {}: {:?} (expected {}, got {})",
+                    part.part, part.status, expected, actual
+                ),
+                _ => println!("  {}: {:?} ({})", part.part, part.status, actual),
+            }
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let config = match &cli.config {
+        Some(path) => Config::load(path),
+        None => Config::from_env(),
+    }
+    .unwrap_or_else(|e| exit_with_error(&e.to_string(), USAGE_ERROR_CODE));
+    let manager = GroupManager::from_config(&config);
+
+    match cli.command {
+        Command::Groups => print_names(manager.get_group_names(), cli.json),
+        Command::Challenges { group } => {
+            let group_name = find_group_name(&manager, &group).unwrap_or_else(|| {
+                exit_with_error(&format!("Unknown group: {}", group), USAGE_ERROR_CODE)
+            });
+            print_names(challenge_names(&manager, &group_name), cli.json);
+        }
+        Command::Solve {
+            group,
+            challenge,
+            input,
+            part,
+            timeout,
+        } => {
+            let challenge = find_challenge(&manager, &group, &challenge);
+            let input = read_input(input).unwrap_or_else(|e| {
+                exit_with_error(&format!("Unable to read input: {}", e), USAGE_ERROR_CODE)
+            });
+            let context = SolveContext::new(match timeout {
+                Some(t) => CancellationToken::with_timeout(t),
+                None => CancellationToken::new(),
+            });
+            let result = match part {
+                Some(p) => challenge.solve_part(&input, p, &context),
+                None => challenge.solve(&input, &context),
+            };
+            match result {
+                Ok(solution) if cli.json => {
+                    println!("{}", serde_json::to_string(&solution).unwrap())
+                }
+                Ok(solution) => println!("{}", solution),
+                Err(e) => match e.downcast_ref::<ChallengeError>().map(ChallengeError::kind) {
+                    Some(ChallengeErrorKind::TimedOut) => {
+                        exit_with_error(&format!("Error:\n{}", e), TIMEOUT_ERROR_CODE)
+                    }
+                    _ => exit_with_error(&format!("Error:\n{}", e), SOLVE_ERROR_CODE),
+                },
+            }
+        }
+        Command::Bench {
+            inputs,
+            runs,
+            group,
+            output,
+            baseline,
+            threshold,
+        } => {
+            let group_name = group.map(|g| {
+                find_group_name(&manager, &g).unwrap_or_else(|| {
+                    exit_with_error(&format!("Unknown group: {}", g), USAGE_ERROR_CODE)
+                })
+            });
+            let store = match inputs {
+                Some(path) => InputStore::new(&path),
+                None => InputStore::from_env(),
+            };
+            let report = bench::run(
+                &manager,
+                &store,
+                runs,
+                group_name.as_deref(),
+                &SolveContext::default,
+            )
+            .unwrap_or_else(|e| exit_with_error(&e.to_string(), UNAVAILABLE_ERROR_CODE));
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            } else {
+                print_report(&report);
+            }
+            if let Some(path) = output {
+                report.save(&path).unwrap_or_else(|e| {
+                    exit_with_error(&format!("Unable to write report: {}", e), USAGE_ERROR_CODE)
+                });
+            }
+            if let Some(path) = baseline {
+                let baseline = BenchReport::load(&path).unwrap_or_else(|e| {
+                    exit_with_error(&format!("Unable to read baseline: {}", e), USAGE_ERROR_CODE)
+                });
+                let regressions = report.compare(&baseline, threshold);
+                for r in &regressions {
+                    eprintln!(
+                        "Regression: {} - {}, {}: {:.3}ms -> {:.3}ms",
+                        r.group,
+                        r.challenge,
+                        part_label(r.part),
+                        r.baseline_ms,
+                        r.current_ms
+                    );
+                }
+                if !regressions.is_empty() {
+                    process::exit(REGRESSION_ERROR_CODE);
+                }
+            }
+        }
+        Command::Verify {
+            inputs,
+            answers,
+            group,
+        } => {
+            let group_name = group.map(|g| {
+                find_group_name(&manager, &g).unwrap_or_else(|| {
+                    exit_with_error(&format!("Unknown group: {}", g), USAGE_ERROR_CODE)
+                })
+            });
+            let store = match inputs {
+                Some(path) => InputStore::new(&path),
+                None => InputStore::from_env(),
+            };
+            let answers = match answers {
+                Some(path) => Answers::load(&path),
+                None => Answers::from_env(&store),
+            }
+            .unwrap_or_else(|e| {
+                exit_with_error(&format!("Unable to read answers: {}", e), USAGE_ERROR_CODE)
+            });
+            let verifications = manager
+                .verify(
+                    &store,
+                    &answers,
+                    group_name.as_deref(),
+                    &SolveContext::default,
+                )
+                .unwrap_or_else(|e| exit_with_error(&e.to_string(), UNAVAILABLE_ERROR_CODE));
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&verifications).unwrap());
+            } else {
+                print_verifications(&verifications);
+            }
+            if verifications.iter().any(|v| v.status() == PartStatus::Fail) {
+                process::exit(VERIFY_ERROR_CODE);
+            }
+        }
+    }
+}
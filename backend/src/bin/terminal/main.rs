@@ -1,14 +1,44 @@
-use challenges::groups::group_manager::GroupManager;
+use std::{
+    collections::HashMap,
+    process,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use challenges::{
+    answers::{Answers, PartStatus},
+    config::Config,
+    groups::{
+        group_manager::GroupManager,
+        health::{GroupHealth, HealthStatus},
+        progress::ProgressReport,
+        search::SearchResult,
+        solve_context::SolveContext,
+    },
+    input_store::{ChallengeKey, InputStore},
+};
 use cursive::{
     align::HAlign,
+    theme::{BaseColor, Color},
     traits::{Nameable, Resizable, View},
+    utils::markup::StyledString,
     view::{IntoBoxedView, SizeConstraint},
     views::{
-        Button, Dialog, LinearLayout, PaddedView, Panel, ScrollView, SelectView, TextArea, TextView,
+        BoxedView, Button, Dialog, EditView, HideableView, LinearLayout, PaddedView, Panel,
+        ScrollView, SelectView, TextArea, TextView,
     },
     Cursive,
 };
 
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// Interval at which the spinner and the elapsed time of a solve are refreshed
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
 fn pad<V>(v: V) -> PaddedView<V> {
     return PaddedView::lrtb(
         1, 1, 1, 1, // Left, Right, Top, Bottom
@@ -17,60 +47,337 @@ fn pad<V>(v: V) -> PaddedView<V> {
 }
 
 struct UserData {
-    group_manager: GroupManager,
+    group_manager: Arc<GroupManager>,
+    input_store: InputStore,
     selected_group: String,
     selected_challenge: Option<String>,
+    // Challenges of the selected group, as last loaded
+    challenge_names: Vec<String>,
+    // Verification status of each challenge, keyed by group and challenge name
+    verifications: HashMap<(String, String), PartStatus>,
 }
 
 impl UserData {
     fn get_group_manager(&self) -> &GroupManager {
         return &self.group_manager;
     }
+
+    /// Key of the saved input of the selected challenge
+    fn selected_key(&self) -> Option<ChallengeKey> {
+        let group = self.group_manager.get_group(&self.selected_group)?;
+        let challenge = self
+            .group_manager
+            .get_challenge(&self.selected_group, self.selected_challenge.as_ref()?)
+            .ok()??;
+        return Some(ChallengeKey::new(group, challenge.as_ref()));
+    }
 }
 
-fn update_view(s: &mut Cursive) {
-    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
-    let challenges = user_data
-        .get_group_manager()
-        .get_group_challenge_names(user_data.selected_group.as_str())
-        .unwrap();
+fn challenge_label(challenge: &str, status: Option<&PartStatus>) -> StyledString {
+    let mut label = StyledString::new();
+    match status {
+        Some(PartStatus::Pass) => label.append_styled("✔ ", Color::Dark(BaseColor::Green)),
+        Some(PartStatus::Fail) => label.append_styled("✘ ", Color::Dark(BaseColor::Red)),
+        _ => {}
+    }
+    label.append_plain(challenge);
+    return label;
+}
 
-    let group_changed = user_data.selected_challenge.is_none();
-    if group_changed {
-        user_data.selected_challenge = Some(challenges[0].clone());
+fn group_label(group: &str, health: &GroupHealth) -> StyledString {
+    let mut label = StyledString::new();
+    match health.status {
+        HealthStatus::Up => {}
+        HealthStatus::Degraded => label.append_styled("◐ ", Color::Dark(BaseColor::Yellow)),
+        HealthStatus::Down => label.append_styled("✘ ", Color::Dark(BaseColor::Red)),
     }
-    let selected_challenge_name = user_data.selected_challenge.clone().unwrap();
-    let selected_challenge = user_data
-        .group_manager
-        .get_challenge(
-            user_data.selected_group.as_str(),
-            selected_challenge_name.as_str(),
-        )
-        .unwrap();
-    let description = selected_challenge.description().to_owned();
-
-    if group_changed {
-        s.call_on_name("challenge_select", |view: &mut SelectView| {
-            view.clear();
-            for (i, challenge) in challenges.iter().enumerate() {
-                view.add_item_str(challenge);
-                if *challenge == selected_challenge_name {
-                    view.set_selection(i);
-                }
+    label.append_plain(group);
+    return label;
+}
+
+/// Relabels the groups with their current health. Remote groups list their challenges to know
+/// it, so it is fetched away from the UI thread
+fn fill_group_select(s: &mut Cursive) {
+    let manager = s.user_data::<UserData>().unwrap().group_manager.clone();
+    let cb_sink = s.cb_sink().clone();
+    thread::spawn(move || {
+        let labels: Vec<(StyledString, String)> = manager
+            .get_group_names()
+            .into_iter()
+            .map(|g| (group_label(&g, &manager.get_group_health(&g).unwrap()), g))
+            .collect();
+        let _ = cb_sink.send(Box::new(move |s: &mut Cursive| set_group_labels(s, labels)));
+    });
+}
+
+fn set_group_labels(s: &mut Cursive, labels: Vec<(StyledString, String)>) {
+    let selected_group = s.user_data::<UserData>().unwrap().selected_group.clone();
+    s.call_on_name("group_select", |view: &mut SelectView| {
+        view.clear();
+        for (i, (label, group)) in labels.into_iter().enumerate() {
+            let selected = group == selected_group;
+            view.add_item(label, group);
+            if selected {
+                view.set_selection(i);
             }
+        }
+    });
+}
+
+fn fill_challenge_select(s: &mut Cursive) {
+    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+    let group = user_data.selected_group.clone();
+    let selected_challenge_name = user_data.selected_challenge.clone().unwrap_or_default();
+    let labels: Vec<(StyledString, String)> = user_data
+        .challenge_names
+        .clone()
+        .into_iter()
+        .map(|c| {
+            let status = user_data.verifications.get(&(group.clone(), c.clone()));
+            return (challenge_label(&c, status), c);
+        })
+        .collect();
+
+    s.call_on_name("challenge_select", |view: &mut SelectView| {
+        view.clear();
+        for (i, (label, challenge)) in labels.into_iter().enumerate() {
+            let selected = challenge == selected_challenge_name;
+            view.add_item(label, challenge);
+            if selected {
+                view.set_selection(i);
+            }
+        }
+    });
+}
+
+/// Shows either the selected challenge or the error panel of an unavailable group
+fn show_group_error(s: &mut Cursive, error: Option<String>) {
+    let available = error.is_none();
+    s.call_on_name("challenge_display", |view: &mut HideableView<BoxedView>| {
+        view.set_visible(available);
+    });
+    s.call_on_name("group_error_panel", |view: &mut HideableView<BoxedView>| {
+        view.set_visible(!available);
+    });
+    if let Some(error) = error {
+        let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+        user_data.selected_challenge = None;
+        user_data.challenge_names = vec![];
+        s.call_on_name("challenge_select", |view: &mut SelectView| view.clear());
+        s.call_on_name("group_error", |view: &mut TextView| {
+            view.set_content(error);
         });
     }
+}
+
+/// What the display shows of the selected challenge of a group
+struct ChallengeDisplay {
+    challenges: Vec<String>,
+    challenge: String,
+    description: String,
+    part_count: usize,
+    saved_input: String,
+}
+
+/// Lists the challenges of the group and loads the selected one, or the first one if none is
+/// selected. The error is shown in place of the challenge
+fn load_challenge_display(
+    manager: &GroupManager,
+    store: &InputStore,
+    group: &str,
+    challenge: Option<String>,
+) -> Result<ChallengeDisplay, String> {
+    let challenges = match manager.get_group_challenge_names(group) {
+        Ok(c) => c.unwrap_or_default(),
+        Err(e) => return Err(e.to_string()),
+    };
+    let challenge = match challenge.or_else(|| challenges.first().cloned()) {
+        Some(c) => c,
+        None => return Err(format!("{} has no challenges", group)),
+    };
+    let config = match manager.get_challenge(group, &challenge) {
+        Ok(Some(c)) => c,
+        Ok(None) => return Err(format!("{} has no challenge {}", group, challenge)),
+        Err(e) => return Err(e.to_string()),
+    };
+    let saved_input = manager
+        .get_group(group)
+        .and_then(|g| {
+            store
+                .load(&ChallengeKey::new(g, config.as_ref()))
+                .unwrap_or_default()
+        })
+        .unwrap_or_default();
+    return Ok(ChallengeDisplay {
+        challenges: challenges,
+        challenge: challenge,
+        description: config.description().to_owned(),
+        part_count: config.part_count(),
+        saved_input: saved_input,
+    });
+}
+
+/// Relabels the groups and loads the selected challenge away from the UI thread, remote groups
+/// may take a while to list their challenges
+fn update_view(s: &mut Cursive) {
+    fill_group_select(s);
+    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+    let manager = user_data.group_manager.clone();
+    let store = user_data.input_store.clone();
+    let group = user_data.selected_group.clone();
+    let challenge = user_data.selected_challenge.clone();
+    let cb_sink = s.cb_sink().clone();
+    thread::spawn(move || {
+        let display = load_challenge_display(&manager, &store, &group, challenge.clone());
+        let _ = cb_sink.send(Box::new(move |s: &mut Cursive| {
+            let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+            // Another group or challenge was selected while loading
+            if user_data.selected_group != group || user_data.selected_challenge != challenge {
+                return;
+            }
+            show_challenge_display(s, display);
+        }));
+    });
+}
+
+fn show_challenge_display(s: &mut Cursive, display: Result<ChallengeDisplay, String>) {
+    let display = match display {
+        Ok(d) => d,
+        Err(e) => return show_group_error(s, Some(e)),
+    };
+    let ChallengeDisplay {
+        challenges,
+        challenge,
+        description,
+        part_count,
+        saved_input,
+    } = display;
+    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+    user_data.selected_challenge = Some(challenge);
+    user_data.challenge_names = challenges;
+    fill_challenge_select(s);
+    show_group_error(s, None);
 
     s.call_on_name("description", |view: &mut TextView| {
         view.set_content(description);
     });
 
     s.call_on_name("input", |view: &mut TextArea| {
-        view.set_content("");
+        view.set_content(saved_input);
+    });
+
+    s.call_on_name("part_buttons", |view: &mut LinearLayout| {
+        while !view.is_empty() {
+            view.remove_child(0);
+        }
+        for part in 1..=part_count {
+            view.add_child(Button::new(format!("Part {}", part), move |s| {
+                solve(s, Some(part))
+            }));
+        }
     });
 }
 
-fn solve(s: &mut Cursive) {
+fn working_status(
+    action: &str,
+    frame: usize,
+    elapsed: Duration,
+    progress: &ProgressReport,
+) -> String {
+    let mut status = format!(
+        "{} {}... {:.1}s",
+        SPINNER_FRAMES[frame % SPINNER_FRAMES.len()],
+        action,
+        elapsed.as_secs_f64()
+    );
+    let description = progress.describe();
+    if !description.is_empty() {
+        status.push_str(&format!("\n{}", description));
+    }
+    return status;
+}
+
+/// Runs the work away from the UI thread behind a dialog showing a spinner, the elapsed time and
+/// the progress reported through the context. The Cancel button cancels the context and closes
+/// the dialog, otherwise `done` gets the result and the elapsed time on the UI thread
+fn run_in_background<T, W, D>(s: &mut Cursive, action: &'static str, work: W, done: D)
+where
+    T: Send + 'static,
+    W: FnOnce(&SolveContext) -> Result<T> + Send + 'static,
+    D: FnOnce(&mut Cursive, Result<T>, Duration) + Send + 'static,
+{
+    let context = SolveContext::default();
+    let token = context.token().clone();
+    let cancel_token = token.clone();
+    s.add_layer(
+        Dialog::around(
+            TextView::new(working_status(
+                action,
+                0,
+                Duration::from_secs(0),
+                &ProgressReport::default(),
+            ))
+            .with_name("working_status"),
+        )
+        .title(action)
+        .button("Cancel", move |s| {
+            cancel_token.cancel();
+            s.pop_layer();
+        }),
+    );
+
+    // The spinner and the result are posted back to the UI thread
+    let cb_sink = s.cb_sink().clone();
+    let work_context = context.clone();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(work(&work_context));
+    });
+    thread::spawn(move || {
+        let start = Instant::now();
+        let mut frame = 0;
+        let result = loop {
+            match receiver.recv_timeout(SPINNER_INTERVAL) {
+                Ok(result) => break result,
+                Err(RecvTimeoutError::Timeout) => {
+                    if token.is_cancelled() {
+                        return;
+                    }
+                    frame += 1;
+                    let status = working_status(
+                        action,
+                        frame,
+                        start.elapsed(),
+                        &context.progress().report(),
+                    );
+                    let _ = cb_sink.send(Box::new(move |s: &mut Cursive| {
+                        s.call_on_name("working_status", |v: &mut TextView| {
+                            v.set_content(status);
+                        });
+                    }));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    break Err(anyhow!("The solver panicked"));
+                }
+            }
+        };
+        let elapsed = start.elapsed();
+        let _ = cb_sink.send(Box::new(move |s: &mut Cursive| {
+            // The dialog was already dismissed by the Cancel button
+            if token.is_cancelled() {
+                return;
+            }
+            s.pop_layer();
+            done(s, result, elapsed);
+        }));
+    });
+}
+
+fn show_error(s: &mut Cursive, error: &anyhow::Error) {
+    s.add_layer(Dialog::around(TextView::new(format!("Error:\n{}", error))).dismiss_button("OK"));
+}
+
+fn solve(s: &mut Cursive, part: Option<usize>) {
     let input = s
         .call_on_name("input", |v: &mut TextArea| {
             return v.get_content().to_owned();
@@ -78,27 +385,147 @@ fn solve(s: &mut Cursive) {
         .unwrap();
 
     let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
-    let selected_challenge = user_data
-        .group_manager
-        .get_challenge(
-            user_data.selected_group.as_str(),
-            user_data.selected_challenge.clone().unwrap().as_str(),
-        )
+    let manager = user_data.group_manager.clone();
+    let group = user_data.selected_group.clone();
+    let challenge = match user_data.selected_challenge.clone() {
+        Some(c) => c,
+        None => return,
+    };
+
+    run_in_background(
+        s,
+        "Solving",
+        move |context| {
+            let challenge = manager
+                .get_challenge(&group, &challenge)?
+                .ok_or_else(|| anyhow!("{} has no challenge {}", group, challenge))?;
+            return match part {
+                Some(p) => challenge.solve_part(&input, p, context),
+                None => challenge.solve(&input, context),
+            };
+        },
+        |s, result, elapsed| {
+            let message = match result {
+                Ok(solution) => {
+                    format!("{}\n\nSolved in {:.3}s", solution, elapsed.as_secs_f64())
+                }
+                Err(e) => format!("Error:\n{}", e),
+            };
+            s.add_layer(
+                Dialog::around(TextView::new(message))
+                    .title("Result")
+                    .dismiss_button("OK"),
+            );
+        },
+    );
+}
+
+fn verify(s: &mut Cursive) {
+    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+    let manager = user_data.group_manager.clone();
+    let store = user_data.input_store.clone();
+    let group = user_data.selected_group.clone();
+
+    run_in_background(
+        s,
+        "Verifying",
+        move |context| {
+            let answers = Answers::from_env(&store)?;
+            return Ok(manager.verify(&store, &answers, Some(group.as_str()), &|| context.clone())?);
+        },
+        |s, result, _| {
+            let verifications = match result {
+                Ok(v) => v,
+                Err(e) => return show_error(s, &e),
+            };
+            let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+            for verification in verifications {
+                user_data.verifications.insert(
+                    (verification.group.clone(), verification.challenge.clone()),
+                    verification.status(),
+                );
+            }
+            fill_challenge_select(s);
+        },
+    );
+}
+
+fn save_input(s: &mut Cursive) {
+    let input = s
+        .call_on_name("input", |v: &mut TextArea| {
+            return v.get_content().to_owned();
+        })
         .unwrap();
-    let message = match selected_challenge.solve(&input) {
-        Ok(solution) => solution,
-        Err(e) => format!("Error:\n{}", e),
+
+    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+    let message = match user_data.selected_key() {
+        Some(key) => match user_data.input_store.save(&key, &input) {
+            Ok(_) => "Input saved".to_owned(),
+            Err(e) => format!("Error:\n{}", e),
+        },
+        None => "Error:\nThe challenge is unavailable".to_owned(),
     };
 
+    s.add_layer(Dialog::around(TextView::new(message)).dismiss_button("OK"));
+}
+
+/// Selects the challenge found by a search in both lists
+fn jump_to(s: &mut Cursive, result: &SearchResult) {
+    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+    user_data.selected_group = result.group.clone();
+    user_data.selected_challenge = Some(result.challenge.clone());
+    update_view(s);
+}
+
+/// Jumps to the only challenge matching the query, or lets the user pick among the matches
+fn search(s: &mut Cursive, query: &str) {
+    s.pop_layer();
+    let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
+    let mut results = user_data.get_group_manager().search(query);
+    if results.is_empty() {
+        let message = format!("No challenge matches \"{}\"", query);
+        s.add_layer(Dialog::around(TextView::new(message)).dismiss_button("OK"));
+        return;
+    }
+    if results.len() == 1 {
+        return jump_to(s, &results.remove(0));
+    }
+    let mut select = SelectView::<SearchResult>::new().on_submit(|s, result| {
+        s.pop_layer();
+        jump_to(s, result);
+    });
+    for result in results {
+        let label = format!("{} ({})", result.challenge, result.group);
+        select.add_item(label, result);
+    }
     s.add_layer(
-        Dialog::around(TextView::new(message))
-            .title("Result")
-            .dismiss_button("OK"),
+        Dialog::around(ScrollView::new(select))
+            .title(format!("Search: {}", query))
+            .dismiss_button("Cancel"),
     );
 }
 
-fn create_group_select(group_names: Vec<String>) -> Box<dyn View> {
-    let mut group_select = SelectView::<String>::new()
+fn show_search_prompt(s: &mut Cursive) {
+    s.add_layer(
+        Dialog::around(
+            EditView::new()
+                .on_submit(search)
+                .with_name("search_query")
+                .fixed_width(30),
+        )
+        .title("Search challenges")
+        .button("Search", |s| {
+            let query = s
+                .call_on_name("search_query", |v: &mut EditView| v.get_content())
+                .unwrap();
+            search(s, &query);
+        })
+        .dismiss_button("Cancel"),
+    );
+}
+
+fn create_group_select() -> Box<dyn View> {
+    let group_select = SelectView::<String>::new()
         .h_align(HAlign::Center)
         .on_select(|s: &mut Cursive, item: &String| {
             let user_data: &mut UserData = s.user_data::<UserData>().unwrap();
@@ -106,9 +533,6 @@ fn create_group_select(group_names: Vec<String>) -> Box<dyn View> {
             user_data.selected_challenge = None;
             update_view(s);
         });
-    for group in group_names {
-        group_select.add_item_str(group);
-    }
     let mut panel = Panel::new(ScrollView::new(
         group_select
             .with_name("group_select")
@@ -136,7 +560,11 @@ fn create_challenge_select() -> Box<dyn View> {
 }
 
 fn create_challenge_display() -> Box<dyn View> {
-    let button = Button::new("Solve", |s| solve(s));
+    let buttons = LinearLayout::horizontal()
+        .child(Button::new("Save input", save_input))
+        .child(Button::new("Verify group", verify))
+        .child(Button::new("Solve", |s| solve(s, None)))
+        .child(LinearLayout::horizontal().with_name("part_buttons"));
     let panel =
         Panel::new(
             LinearLayout::vertical()
@@ -159,29 +587,64 @@ fn create_challenge_display() -> Box<dyn View> {
                     )
                     .resized(SizeConstraint::Full, SizeConstraint::Full),
                 )
-                .child(button),
+                .child(buttons),
         );
-    return pad(panel.resized(SizeConstraint::Full, SizeConstraint::Full)).as_boxed_view();
+    return HideableView::new(BoxedView::new(
+        pad(panel.resized(SizeConstraint::Full, SizeConstraint::Full)).as_boxed_view(),
+    ))
+    .with_name("challenge_display")
+    .as_boxed_view();
+}
+
+fn create_group_error_panel() -> Box<dyn View> {
+    let mut panel = Panel::new(TextView::new("").with_name("group_error"));
+    panel.set_title("Group unavailable");
+    return HideableView::new(BoxedView::new(
+        pad(panel.resized(SizeConstraint::Full, SizeConstraint::Full)).as_boxed_view(),
+    ))
+    .hidden()
+    .with_name("group_error_panel")
+    .as_boxed_view();
 }
 
 fn main() {
-    let mut siv = cursive::default();
-    let group_manager = GroupManager::new();
+    let config = Config::from_env().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let group_manager = GroupManager::from_config(&config);
     let group_names = group_manager.get_group_names();
-    let first_group = group_names[0].as_str();
+    if group_names.is_empty() {
+        eprintln!("No groups are enabled in the config");
+        process::exit(1);
+    }
+    let first_group = group_names[0].clone();
+
+    let mut siv = cursive::default();
     siv.set_user_data(UserData {
-        group_manager: GroupManager::new(),
-        selected_group: first_group.to_owned(),
+        group_manager: Arc::new(group_manager),
+        input_store: InputStore::from_env(),
+        selected_group: first_group,
         selected_challenge: None,
+        challenge_names: vec![],
+        verifications: HashMap::new(),
     });
 
     let linear_layout = LinearLayout::horizontal()
-        .child(create_group_select(group_names))
+        .child(create_group_select())
         .child(create_challenge_select())
         .child(create_challenge_display())
+        .child(create_group_error_panel())
         .full_screen();
     siv.add_fullscreen_layer(linear_layout);
+    // The health of the groups is added to their labels once it is known
+    let labels = group_names
+        .into_iter()
+        .map(|g| (StyledString::plain(g.as_str()), g))
+        .collect();
+    set_group_labels(&mut siv, labels);
     update_view(&mut siv);
+    siv.add_global_callback('/', show_search_prompt);
 
     siv.set_autorefresh(false);
     siv.run();
@@ -0,0 +1,810 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Certificate, Url,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    groups::registry::builtin_groups,
+    utils::{is_slug, slugify},
+};
+
+const DEFAULT_CONFIG_FILE: &str = "challenges.toml";
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_CACHE_TTL_SECS: u64 = 60;
+const DEFAULT_LIST_RETRIES: u32 = 2;
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 200;
+const DEFAULT_BREAKER_THRESHOLD: u32 = 5;
+const DEFAULT_BREAKER_COOLDOWN_SECS: u64 = 30;
+const DEFAULT_WASM_FUEL: u64 = 10_000_000_000;
+const DEFAULT_WASM_MEMORY_LIMIT_MB: u64 = 256;
+
+/// Protocol used to solve the challenges of a remote group
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteProtocol {
+    /// Plain text input posted to `/solve/<id>`, answered with "Name: value" lines
+    Text,
+    /// JSON request and response posted to `/v1/solve/<id>`, see `remote_protocol`
+    V1,
+}
+
+impl Default for RemoteProtocol {
+    fn default() -> Self {
+        return RemoteProtocol::V1;
+    }
+}
+
+/// Group whose challenges are listed and solved by a separate service
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteGroupConfig {
+    pub name: String,
+    /// Stable id used in the API paths, the slug of the name when absent
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Page describing the challenges
+    pub url: String,
+    /// URL under which the service exposes `/list/` and `/solve/<id>`, as
+    /// `https://example.com:8443/aoc-2019`
+    pub base_url: String,
+    #[serde(default)]
+    pub protocol: RemoteProtocol,
+    /// Seconds before a request without an answer fails
+    #[serde(default = "default_timeout")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_connect_timeout")]
+    pub connect_timeout_secs: u64,
+    /// Times a failed listing is retried, waiting `retry_backoff_ms` then twice as long each time.
+    /// Solves are never retried
+    #[serde(default = "default_list_retries")]
+    pub list_retries: u32,
+    #[serde(default = "default_retry_backoff")]
+    pub retry_backoff_ms: u64,
+    /// Consecutive failed requests after which requests fail right away for
+    /// `breaker_cooldown_secs`, 0 to never stop sending requests
+    #[serde(default = "default_breaker_threshold")]
+    pub breaker_threshold: u32,
+    #[serde(default = "default_breaker_cooldown")]
+    pub breaker_cooldown_secs: u64,
+    /// Seconds during which the challenge listing is served from the cache. Once expired, the
+    /// cached listing is still served while a new one is fetched in the background
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl_secs: u64,
+    /// PEM file of the certificate authorities trusted for HTTPS, on top of the system ones
+    #[serde(default)]
+    pub ca_bundle: Option<PathBuf>,
+    /// Token sent as `Authorization: Bearer <token>`
+    #[serde(default)]
+    pub bearer_token: Option<String>,
+    /// Header sent with every request, as `X-Api-Key: <key>`
+    #[serde(default)]
+    pub auth_header: Option<String>,
+}
+
+/// Id of a configured group, the slug of its name unless set
+fn group_id(name: &str, id: &Option<String>) -> String {
+    return id.clone().unwrap_or_else(|| slugify(name));
+}
+
+/// Reports an id that isn't a slug, or a name no id can be derived from
+fn validate_id(label: &str, name: &str, id: &Option<String>, errors: &mut Vec<String>) {
+    match id {
+        Some(id) if !is_slug(id) => errors.push(format!(
+            "{}: invalid id \"{}\", expected lowercase letters and digits separated by single dashes",
+            label, id
+        )),
+        None if !name.trim().is_empty() && slugify(name).is_empty() => errors.push(format!(
+            "{}: the name has no letter or digit to derive the id from, set an id",
+            label
+        )),
+        _ => {}
+    }
+}
+
+/// Reports a group whose name or id is taken by a previous group, as groups are looked up by both
+fn validate_unique(
+    label: &str,
+    name: &str,
+    id: String,
+    taken: &mut Vec<(String, String)>,
+    errors: &mut Vec<String>,
+) {
+    if taken.iter().any(|(n, _)| n == name) {
+        errors.push(format!("{}: another group has the name {}", label, name));
+    }
+    if taken.iter().any(|(_, i)| i == &id) {
+        errors.push(format!("{}: another group has the id {}", label, id));
+    }
+    taken.push((name.to_owned(), id));
+}
+
+fn default_timeout() -> u64 {
+    return DEFAULT_TIMEOUT_SECS;
+}
+
+fn default_connect_timeout() -> u64 {
+    return DEFAULT_CONNECT_TIMEOUT_SECS;
+}
+
+fn default_cache_ttl() -> u64 {
+    return DEFAULT_CACHE_TTL_SECS;
+}
+
+fn default_list_retries() -> u32 {
+    return DEFAULT_LIST_RETRIES;
+}
+
+fn default_retry_backoff() -> u64 {
+    return DEFAULT_RETRY_BACKOFF_MS;
+}
+
+fn default_breaker_threshold() -> u32 {
+    return DEFAULT_BREAKER_THRESHOLD;
+}
+
+fn default_breaker_cooldown() -> u64 {
+    return DEFAULT_BREAKER_COOLDOWN_SECS;
+}
+
+impl Default for RemoteGroupConfig {
+    fn default() -> Self {
+        return RemoteGroupConfig {
+            name: String::new(),
+            id: None,
+            url: String::new(),
+            base_url: String::new(),
+            protocol: RemoteProtocol::default(),
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT_SECS,
+            list_retries: DEFAULT_LIST_RETRIES,
+            retry_backoff_ms: DEFAULT_RETRY_BACKOFF_MS,
+            breaker_threshold: DEFAULT_BREAKER_THRESHOLD,
+            breaker_cooldown_secs: DEFAULT_BREAKER_COOLDOWN_SECS,
+            cache_ttl_secs: DEFAULT_CACHE_TTL_SECS,
+            ca_bundle: None,
+            bearer_token: None,
+            auth_header: None,
+        };
+    }
+}
+
+impl RemoteGroupConfig {
+    /// Remote group on localhost, the port being overridden by the environment variable if set
+    fn local(name: &str, url: &str, default_port: u16, port_envvar: &str) -> Self {
+        let port = match std::env::var(port_envvar) {
+            Ok(p) => p.parse::<u16>().unwrap_or(default_port),
+            Err(_) => default_port,
+        };
+        return RemoteGroupConfig {
+            name: name.to_owned(),
+            url: url.to_owned(),
+            base_url: format!("http://localhost:{}", port),
+            // The 2018 and 2019 modules predate the JSON protocol
+            protocol: RemoteProtocol::Text,
+            ..RemoteGroupConfig::default()
+        };
+    }
+
+    pub fn id(&self) -> String {
+        return group_id(&self.name, &self.id);
+    }
+
+    /// URL of the path under the base URL
+    pub fn endpoint(&self, path: &str) -> String {
+        return format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+    }
+
+    /// Splits the auth header into its name and value
+    pub fn auth_header(&self) -> Option<(&str, &str)> {
+        let header = self.auth_header.as_ref()?;
+        let mut split = header.splitn(2, ':');
+        return Some((split.next()?.trim(), split.next()?.trim()));
+    }
+
+    /// Certificate authority loaded from the CA bundle
+    pub fn certificate(&self) -> Result<Option<Certificate>> {
+        return match &self.ca_bundle {
+            Some(path) => Ok(Some(Certificate::from_pem(&fs::read(path)?)?)),
+            None => Ok(None),
+        };
+    }
+
+    fn validate(&self, errors: &mut Vec<String>) {
+        let name = if self.name.trim().is_empty() {
+            errors.push("A remote group has an empty name".to_owned());
+            "<unnamed>"
+        } else {
+            self.name.as_str()
+        };
+        validate_id(
+            &format!("Remote group {}", name),
+            &self.name,
+            &self.id,
+            errors,
+        );
+        match Url::parse(&self.base_url) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {}
+            Ok(url) => errors.push(format!(
+                "Remote group {}: unsupported scheme \"{}\", expected http or https",
+                name,
+                url.scheme()
+            )),
+            Err(e) => errors.push(format!(
+                "Remote group {}: invalid base URL \"{}\": {}",
+                name, self.base_url, e
+            )),
+        }
+        if self.timeout_secs == 0 || self.connect_timeout_secs == 0 {
+            errors.push(format!(
+                "Remote group {}: the timeouts must be greater than 0",
+                name
+            ));
+        }
+        if let Some(path) = &self.ca_bundle {
+            if let Err(e) = self.certificate() {
+                errors.push(format!(
+                    "Remote group {}: invalid CA bundle {}: {}",
+                    name,
+                    path.display(),
+                    e
+                ));
+            }
+        }
+        if let Some(token) = &self.bearer_token {
+            if token.trim().is_empty() || HeaderValue::from_str(token).is_err() {
+                errors.push(format!("Remote group {}: invalid bearer token", name));
+            }
+            if self
+                .auth_header()
+                .is_some_and(|(n, _)| n.eq_ignore_ascii_case("Authorization"))
+            {
+                errors.push(format!(
+                    "Remote group {}: the bearer token and the auth header both set Authorization",
+                    name
+                ));
+            }
+        }
+        if let Some(header) = &self.auth_header {
+            let valid = match self.auth_header() {
+                Some((n, v)) => {
+                    HeaderName::from_bytes(n.as_bytes()).is_ok() && HeaderValue::from_str(v).is_ok()
+                }
+                None => false,
+            };
+            if !valid {
+                errors.push(format!(
+                    "Remote group {}: invalid auth header \"{}\", expected \"Name: value\"",
+                    name, header
+                ));
+            }
+        }
+    }
+}
+
+/// Group whose challenges are listed and solved by a local executable, speaking JSON lines on
+/// its standard input and output, see `ProcessRequest`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommandGroupConfig {
+    pub name: String,
+    /// Stable id used in the API paths, the slug of the name when absent
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Page describing the challenges
+    pub url: String,
+    /// Executable, looked up in the PATH when it has no directory
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Directory the executable is started in, the current one when absent
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    /// Seconds before a request without an answer fails, the process is then restarted
+    #[serde(default = "default_timeout")]
+    pub timeout_secs: u64,
+}
+
+impl Default for CommandGroupConfig {
+    fn default() -> Self {
+        return CommandGroupConfig {
+            name: String::new(),
+            id: None,
+            url: String::new(),
+            command: String::new(),
+            args: vec![],
+            working_dir: None,
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+        };
+    }
+}
+
+impl CommandGroupConfig {
+    pub fn id(&self) -> String {
+        return group_id(&self.name, &self.id);
+    }
+
+    fn validate(&self, errors: &mut Vec<String>) {
+        let name = if self.name.trim().is_empty() {
+            errors.push("A command group has an empty name".to_owned());
+            "<unnamed>"
+        } else {
+            self.name.as_str()
+        };
+        validate_id(
+            &format!("Command group {}", name),
+            &self.name,
+            &self.id,
+            errors,
+        );
+        if self.command.trim().is_empty() {
+            errors.push(format!("Command group {}: the command is empty", name));
+        }
+        if let Some(dir) = &self.working_dir {
+            if !dir.is_dir() {
+                errors.push(format!(
+                    "Command group {}: the working directory {} does not exist",
+                    name,
+                    dir.display()
+                ));
+            }
+        }
+        if self.timeout_secs == 0 {
+            errors.push(format!(
+                "Command group {}: the timeout must be greater than 0",
+                name
+            ));
+        }
+    }
+}
+
+/// Group whose challenges are listed and solved by a WebAssembly module, run in a sandbox, see
+/// `WasmGroupConfig`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WasmModuleConfig {
+    pub name: String,
+    /// Stable id used in the API paths, the slug of the name when absent
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Page describing the challenges
+    pub url: String,
+    /// `.wasm` file of the module
+    pub path: PathBuf,
+    /// Fuel given to each request, roughly the number of instructions it may execute
+    #[serde(default = "default_wasm_fuel")]
+    pub fuel: u64,
+    /// Memory the module may grow to during a request
+    #[serde(default = "default_wasm_memory_limit")]
+    pub memory_limit_mb: u64,
+}
+
+fn default_wasm_fuel() -> u64 {
+    return DEFAULT_WASM_FUEL;
+}
+
+fn default_wasm_memory_limit() -> u64 {
+    return DEFAULT_WASM_MEMORY_LIMIT_MB;
+}
+
+impl Default for WasmModuleConfig {
+    fn default() -> Self {
+        return WasmModuleConfig {
+            name: String::new(),
+            id: None,
+            url: String::new(),
+            path: PathBuf::new(),
+            fuel: DEFAULT_WASM_FUEL,
+            memory_limit_mb: DEFAULT_WASM_MEMORY_LIMIT_MB,
+        };
+    }
+}
+
+impl WasmModuleConfig {
+    pub fn id(&self) -> String {
+        return group_id(&self.name, &self.id);
+    }
+
+    fn validate(&self, errors: &mut Vec<String>) {
+        let name = if self.name.trim().is_empty() {
+            errors.push("A WebAssembly group has an empty name".to_owned());
+            "<unnamed>"
+        } else {
+            self.name.as_str()
+        };
+        validate_id(
+            &format!("WebAssembly group {}", name),
+            &self.name,
+            &self.id,
+            errors,
+        );
+        if !self.path.is_file() {
+            errors.push(format!(
+                "WebAssembly group {}: the module {} does not exist",
+                name,
+                self.path.display()
+            ));
+        }
+        if self.fuel == 0 || self.memory_limit_mb == 0 {
+            errors.push(format!(
+                "WebAssembly group {}: the fuel and memory limit must be greater than 0",
+                name
+            ));
+        }
+    }
+}
+
+/// Groups loaded by the binaries, read from a TOML file:
+///
+/// ```toml
+/// builtin_groups = ["Advent of Code 2020"]
+/// plugins_dir = "plugins"
+///
+/// [[remote_groups]]
+/// name = "Advent of Code 2019"
+/// url = "https://adventofcode.com/2019"
+/// base_url = "https://example.com/aoc-2019"
+/// protocol = "v1"
+/// timeout_secs = 30
+/// cache_ttl_secs = 60
+/// ca_bundle = "ca.pem"
+/// bearer_token = "<token>"
+/// auth_header = "X-Api-Key: <key>"
+///
+/// [[command_groups]]
+/// name = "Advent of Code 2017"
+/// url = "https://adventofcode.com/2017"
+/// command = "python3"
+/// args = ["aoc_2017.py"]
+///
+/// [[wasm_groups]]
+/// name = "Advent of Code 2016"
+/// url = "https://adventofcode.com/2016"
+/// path = "aoc_2016.wasm"
+/// fuel = 10000000000
+/// memory_limit_mb = 256
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Names of the enabled built-in groups, all of them when absent
+    #[serde(default)]
+    pub builtin_groups: Option<Vec<String>>,
+    #[serde(default)]
+    pub remote_groups: Vec<RemoteGroupConfig>,
+    #[serde(default)]
+    pub command_groups: Vec<CommandGroupConfig>,
+    #[serde(default)]
+    pub wasm_groups: Vec<WasmModuleConfig>,
+    /// Directory of the group plugins, the libraries built from `cdylib` crates calling
+    /// `export_group!`
+    #[serde(default)]
+    pub plugins_dir: Option<PathBuf>,
+}
+
+/// Every problem found in a config file
+#[derive(Debug, Error)]
+pub struct ConfigError {
+    source_name: String,
+    errors: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid config {}:", self.source_name)?;
+        for error in &self.errors {
+            write!(f, "\n  - {}", error)?;
+        }
+        return Ok(());
+    }
+}
+
+impl Default for Config {
+    /// The modules of this repository, on the ports set by $CHALLENGES_AOC_<year>_PORT
+    fn default() -> Self {
+        return Config {
+            builtin_groups: None,
+            remote_groups: vec![
+                RemoteGroupConfig::local(
+                    "Advent of Code 2018",
+                    "https://adventofcode.com/2018",
+                    8083,
+                    "CHALLENGES_AOC_2018_PORT",
+                ),
+                RemoteGroupConfig::local(
+                    "Advent of Code 2019",
+                    "https://adventofcode.com/2019",
+                    8082,
+                    "CHALLENGES_AOC_2019_PORT",
+                ),
+            ],
+            command_groups: vec![],
+            wasm_groups: vec![],
+            plugins_dir: None,
+        };
+    }
+}
+
+impl Config {
+    /// Parses and validates the config, `source_name` is used in the error messages
+    pub fn parse(content: &str, source_name: &str) -> Result<Self> {
+        let config: Config = match toml::from_str(content) {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(ConfigError {
+                    source_name: source_name.to_owned(),
+                    errors: vec![e.to_string().trim().to_owned()],
+                }
+                .into())
+            }
+        };
+        let errors = config.validate();
+        if !errors.is_empty() {
+            return Err(ConfigError {
+                source_name: source_name.to_owned(),
+                errors: errors,
+            }
+            .into());
+        }
+        return Ok(config);
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Unable to read config {}: {}",
+                    path.display(),
+                    e
+                ))
+            }
+        };
+        return Config::parse(&content, &path.display().to_string());
+    }
+
+    /// Config at $CHALLENGES_CONFIG, or ./challenges.toml if it exists, or the default groups
+    pub fn from_env() -> Result<Self> {
+        return match std::env::var("CHALLENGES_CONFIG") {
+            Ok(path) => Config::load(Path::new(&path)),
+            Err(_) if PathBuf::from(DEFAULT_CONFIG_FILE).exists() => {
+                Config::load(Path::new(DEFAULT_CONFIG_FILE))
+            }
+            Err(_) => Ok(Config::default()),
+        };
+    }
+
+    pub fn is_builtin_enabled(&self, name: &str) -> bool {
+        return match &self.builtin_groups {
+            Some(names) => names.iter().any(|n| n == name),
+            None => true,
+        };
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        let builtin_groups = builtin_groups();
+        for name in self.builtin_groups.iter().flatten() {
+            if !builtin_groups.iter().any(|g| g.name == name) {
+                errors.push(format!(
+                    "Unknown built-in group \"{}\", available groups: {}",
+                    name,
+                    builtin_groups
+                        .iter()
+                        .map(|g| g.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+        let mut taken: Vec<(String, String)> = builtin_groups
+            .iter()
+            .filter(|g| self.is_builtin_enabled(g.name))
+            .map(|g| (g.name.to_owned(), g.id.to_owned()))
+            .collect();
+        for group in &self.remote_groups {
+            group.validate(&mut errors);
+            let label = format!("Remote group {}", group.name);
+            validate_unique(&label, &group.name, group.id(), &mut taken, &mut errors);
+        }
+        if let Some(dir) = &self.plugins_dir {
+            if !dir.is_dir() {
+                errors.push(format!(
+                    "The plugins directory {} does not exist",
+                    dir.display()
+                ));
+            }
+        }
+        for group in &self.command_groups {
+            group.validate(&mut errors);
+            let label = format!("Command group {}", group.name);
+            validate_unique(&label, &group.name, group.id(), &mut taken, &mut errors);
+        }
+        for group in &self.wasm_groups {
+            group.validate(&mut errors);
+            let label = format!("WebAssembly group {}", group.name);
+            validate_unique(&label, &group.name, group.id(), &mut taken, &mut errors);
+        }
+        return errors;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let config = Config::parse(
+            r#"
+            builtin_groups = []
+
+            [[remote_groups]]
+            name = "Advent of Code 2019"
+            id = "aoc-2019"
+            url = "https://adventofcode.com/2019"
+            base_url = "https://example.com:8443/aoc-2019/"
+            bearer_token = "abc"
+            auth_header = "X-Api-Key: def"
+
+            [[command_groups]]
+            name = "Advent of Code 2017"
+            url = "https://adventofcode.com/2017"
+            command = "python3"
+            args = ["aoc_2017.py"]
+            "#,
+            "test",
+        )
+        .unwrap();
+        assert!(!config.is_builtin_enabled("Advent of Code 2020"));
+        assert_eq!(config.remote_groups.len(), 1);
+        assert_eq!(config.remote_groups[0].timeout_secs, DEFAULT_TIMEOUT_SECS);
+        assert_eq!(
+            config.remote_groups[0].cache_ttl_secs,
+            DEFAULT_CACHE_TTL_SECS
+        );
+        assert_eq!(config.remote_groups[0].protocol, RemoteProtocol::V1);
+        assert_eq!(config.remote_groups[0].id(), "aoc-2019");
+        assert_eq!(config.command_groups[0].id(), "advent-of-code-2017");
+        assert_eq!(
+            config.remote_groups[0].auth_header(),
+            Some(("X-Api-Key", "def"))
+        );
+        assert_eq!(
+            config.remote_groups[0].endpoint("/list/"),
+            "https://example.com:8443/aoc-2019/list/"
+        );
+        assert_eq!(
+            config.command_groups,
+            vec![CommandGroupConfig {
+                name: "Advent of Code 2017".to_owned(),
+                url: "https://adventofcode.com/2017".to_owned(),
+                command: "python3".to_owned(),
+                args: vec!["aoc_2017.py".to_owned()],
+                ..CommandGroupConfig::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn default_is_valid() {
+        assert!(Config::default().validate().is_empty());
+    }
+
+    #[rstest(
+        content,
+        expected_error,
+        case("builtin_groups = [\"Unknown\"]", "Unknown built-in group \"Unknown\""),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"",
+            "missing field `base_url`"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"localhost:8082\"",
+            "unsupported scheme \"localhost\""
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://\"",
+            "invalid base URL"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\ntimeout_secs = 0",
+            "timeouts must be greater than 0"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\nconnect_timeout_secs = 0",
+            "timeouts must be greater than 0"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\nca_bundle = \"missing.pem\"",
+            "invalid CA bundle missing.pem"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\nbearer_token = \"a\"\nauth_header = \"authorization: b\"",
+            "both set Authorization"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\nauth_header = \"abc\"",
+            "invalid auth header"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"Advent of Code 2020\"\nurl = \"\"\nbase_url = \"http://a:1\"",
+            "another group has the id advent-of-code-2020"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nid = \"Day 1\"\nurl = \"\"\nbase_url = \"http://a:1\"",
+            "invalid id \"Day 1\""
+        ),
+        case(
+            "[[remote_groups]]\nname = \"???\"\nurl = \"\"\nbase_url = \"http://a:1\"",
+            "the name has no letter or digit to derive the id from"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\nprotocol = \"xml\"",
+            "unknown variant `xml`"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\nport = 1",
+            "unknown field `port`"
+        ),
+        case(
+            "[[command_groups]]\nname = \"A\"\nurl = \"\"\ncommand = \" \"",
+            "the command is empty"
+        ),
+        case(
+            "[[command_groups]]\nname = \"A\"\nurl = \"\"\ncommand = \"a\"\nworking_dir = \"missing\"",
+            "the working directory missing does not exist"
+        ),
+        case(
+            "[[command_groups]]\nname = \"A\"\nurl = \"\"\ncommand = \"a\"\ntimeout_secs = 0",
+            "timeout must be greater than 0"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\n[[command_groups]]\nname = \"a\"\nurl = \"\"\ncommand = \"a\"",
+            "Command group a: another group has the id a"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nurl = \"\"\nbase_url = \"http://a:1\"\n[[wasm_groups]]\nname = \"B\"\nid = \"a\"\nurl = \"\"\npath = \"Cargo.toml\"",
+            "WebAssembly group B: another group has the id a"
+        ),
+        case(
+            "[[remote_groups]]\nname = \"A\"\nid = \"a\"\nurl = \"\"\nbase_url = \"http://a:1\"\n[[command_groups]]\nname = \"A\"\nid = \"b\"\nurl = \"\"\ncommand = \"a\"",
+            "Command group A: another group has the name A"
+        ),
+        case(
+            "[[wasm_groups]]\nname = \"Advent of Code 2020\"\nid = \"aoc-2020\"\nurl = \"\"\npath = \"Cargo.toml\"",
+            "WebAssembly group Advent of Code 2020: another group has the name Advent of Code 2020"
+        ),
+        case(
+            "[[wasm_groups]]\nname = \"A\"\nurl = \"\"\npath = \"missing.wasm\"",
+            "the module missing.wasm does not exist"
+        ),
+        case(
+            "[[wasm_groups]]\nname = \"A\"\nurl = \"\"\npath = \"Cargo.toml\"\nfuel = 0",
+            "the fuel and memory limit must be greater than 0"
+        ),
+        case(
+            "plugins_dir = \"missing\"",
+            "The plugins directory missing does not exist"
+        )
+    )]
+    fn invalid(content: &str, expected_error: &str) {
+        let error = Config::parse(content, "test").unwrap_err().to_string();
+        assert!(error.starts_with("Invalid config test:"), "{}", error);
+        assert!(error.contains(expected_error), "{}", error);
+    }
+}
@@ -1,10 +1,20 @@
 use anyhow::Result;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day1 {}
 
+register_challenge!(AdventOfCode2020, Day1);
+
 impl Day1 {
+    pub const TITLE: &'static str = "Day 1: Report Repair";
+    pub const ID: &'static str = "day-1-report-repair";
+
     fn solve_part_one(&self, numbers: Vec<isize>) -> Result<isize> {
         for (x, first) in numbers.iter().enumerate() {
             for (y, second) in numbers.iter().enumerate() {
@@ -38,7 +48,15 @@ impl Day1 {
 
 impl ChallengeConfig for Day1 {
     fn title(&self) -> &str {
-        return "Day 1: Report Repair";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["search", "combinatorics"];
     }
 
     fn description(&self) -> &str {
@@ -46,7 +64,7 @@ impl ChallengeConfig for Day1 {
 Part 2: What is the product of the three entries that sum to 2020?";
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let numbers: Vec<isize> = input
             .split("\n")
             .map(|x| x.trim())
@@ -54,9 +72,11 @@ Part 2: What is the product of the three entries that sum to 2020?";
             .map(|x| x.parse::<isize>())
             .collect::<Result<Vec<isize>, _>>()?;
 
-        let part_one = self.solve_part_one(numbers.clone())?;
-        let part_two = self.solve_part_two(numbers.clone())?;
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return match part {
+            1 => Ok(Solution::for_part(part, self.solve_part_one(numbers)?)),
+            2 => Ok(Solution::for_part(part, self.solve_part_two(numbers)?)),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -98,14 +118,21 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day1 = Day1 {};
-        assert_eq!(day1.solve(input).unwrap(), expected);
+        assert_eq!(
+            day1.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 
     #[test]
     fn parsing_error() {
         let day1 = Day1 {};
         assert_eq!(
-            day1.solve("abc").unwrap_err().to_string(),
+            day1.solve("abc", &SolveContext::default())
+                .unwrap_err()
+                .to_string(),
             "invalid digit found in string"
         );
     }
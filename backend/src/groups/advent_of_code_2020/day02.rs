@@ -1,9 +1,16 @@
 use anyhow::Result;
 use regex::Regex;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day2 {}
+
+register_challenge!(AdventOfCode2020, Day2);
 #[derive(Clone)]
 struct Line {
     min_chars: usize,
@@ -19,27 +26,33 @@ impl Line {
                 Regex::new(r"(?P<min>\d+)-(?P<max>\d+)\s(?P<letter>\w{1}):\s(?P<password>\w+)")
                     .unwrap();
         }
-        for caps in RE.captures_iter(line) {
-            return Ok(Line {
-                min_chars: (&caps["min"]).parse::<usize>().unwrap(),
-                max_chars: (&caps["max"]).parse::<usize>().unwrap(),
-                letter: (&caps["letter"]).to_owned().chars().nth(0).unwrap(),
-                password: (&caps["password"]).to_owned(),
-            });
-        }
-        return Err(ChallengeError::new(
-            ("Could not parse line: ".to_owned() + line).as_str(),
-        ));
+        let caps = match RE.captures(line) {
+            Some(caps) => caps,
+            None => {
+                return Err(ChallengeError::new(
+                    ("Could not parse line: ".to_owned() + line).as_str(),
+                ))
+            }
+        };
+        return Ok(Line {
+            min_chars: caps["min"].parse::<usize>().unwrap(),
+            max_chars: caps["max"].parse::<usize>().unwrap(),
+            letter: caps["letter"].chars().next().unwrap(),
+            password: caps["password"].to_owned(),
+        });
     }
 }
 
 impl Day2 {
+    pub const TITLE: &'static str = "Day 2: Password Philosophy";
+    pub const ID: &'static str = "day-2-password-philosophy";
+
     fn solve_part_one(&self, lines: Vec<Line>) -> i32 {
         let mut total = 0;
         for line in lines {
             let char_count = line.password.matches(line.letter).count();
             if line.min_chars <= char_count && char_count <= line.max_chars {
-                total = total + 1;
+                total += 1;
             }
         }
         return total;
@@ -51,7 +64,7 @@ impl Day2 {
             if (line.password.chars().nth(line.min_chars - 1).unwrap() == line.letter)
                 ^ (line.password.chars().nth(line.max_chars - 1).unwrap() == line.letter)
             {
-                total = total + 1;
+                total += 1;
             }
         }
         return total;
@@ -60,10 +73,18 @@ impl Day2 {
 
 impl ChallengeConfig for Day2 {
     fn title(&self) -> &str {
-        return "Day 2: Password Philosophy";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["parsing", "validation"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let lines: Vec<Line> = input
             .split("\n")
             .map(|x| x.trim())
@@ -71,9 +92,11 @@ impl ChallengeConfig for Day2 {
             .map(Line::parse)
             .collect::<Result<_, _>>()?;
 
-        let part_one = self.solve_part_one(lines.clone());
-        let part_two = self.solve_part_two(lines.clone());
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return match part {
+            1 => Ok(Solution::for_part(part, self.solve_part_one(lines))),
+            2 => Ok(Solution::for_part(part, self.solve_part_two(lines))),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -95,6 +118,11 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day = Day2 {};
-        assert_eq!(day.solve(input).unwrap(), expected);
+        assert_eq!(
+            day.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
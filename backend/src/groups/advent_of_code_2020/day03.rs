@@ -1,11 +1,21 @@
 use anyhow::Result;
 use ndarray::{Array, Array2, Axis};
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day3 {}
 
+register_challenge!(AdventOfCode2020, Day3);
+
 impl Day3 {
+    pub const TITLE: &'static str = "Day 3: Toboggan Trajectory";
+    pub const ID: &'static str = "day-3-toboggan-trajectory";
+
     fn solve_path(&self, array: Array2<usize>, path: (i32, i32)) -> usize {
         let mut trees_hit = 0;
         let mut current_row = 0;
@@ -26,10 +36,18 @@ impl Day3 {
 
 impl ChallengeConfig for Day3 {
     fn title(&self) -> &str {
-        return "Day 3: Toboggan Trajectory";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["grid"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let lines: Vec<&str> = input
             .split("\n")
             .map(|x| x.trim())
@@ -48,32 +66,27 @@ impl ChallengeConfig for Day3 {
             ));
         }
 
-        let part_one_vecs = vec![(-1, 3)];
-        let part_two_vecs = vec![(-1, 1), (-1, 3), (-1, 5), (-1, 7), (-2, 1)];
+        let vecs = match part {
+            1 => vec![(-1, 3)],
+            2 => vec![(-1, 1), (-1, 3), (-1, 5), (-1, 7), (-2, 1)],
+            _ => return Err(ChallengeError::invalid_part(part).into()),
+        };
 
-        let mut result = "".to_string();
-        for (index, vecs) in vec![part_one_vecs, part_two_vecs].iter().enumerate() {
-            let mut line_results = vec![];
-            for path_vec in vecs.iter() {
-                let line_result = self.solve_path(array.clone(), path_vec.clone());
-                line_results.push(line_result);
-            }
-            result.push_str(
-                format!(
-                    "Part {}: {} = {}\n",
-                    index + 1,
-                    line_results
-                        .iter()
-                        .map(usize::to_string)
-                        .collect::<Vec<String>>()
-                        .join(" * "),
-                    line_results.iter().product::<usize>()
-                )
-                .as_str(),
-            );
+        let mut line_results = vec![];
+        for path_vec in vecs.iter() {
+            let line_result = self.solve_path(array.clone(), *path_vec);
+            line_results.push(line_result);
         }
-
-        return Ok(String::from(result.trim_end()));
+        return Ok(Solution::new().with_part_notes(
+            format!("Part {}", part).as_str(),
+            line_results.iter().product::<usize>(),
+            line_results
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<String>>()
+                .join(" * ")
+                .as_str(),
+        ));
     }
 }
 
@@ -98,11 +111,16 @@ mod tests {
             #.##...#...
             #...##....#
             .#..#...#.#",
-            "Part 1: 7 = 7\nPart 2: 2 * 7 * 3 * 4 * 2 = 336"
+            "Part 1: 7 (7)\nPart 2: 336 (2 * 7 * 3 * 4 * 2)"
         )
     )]
     fn solve(map: &str, expected: &str) {
         let day = Day3 {};
-        assert_eq!(day.solve(map).unwrap(), expected);
+        assert_eq!(
+            day.solve(map, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -3,12 +3,29 @@ use std::collections::HashMap;
 use anyhow::Result;
 use regex::Regex;
 
-use crate::{groups::challenge_config::ChallengeConfig, utils::InputUtils};
+use super::config::AdventOfCode2020;
+use crate::{
+    groups::{
+        challenge_config::{ChallengeConfig, ChallengeError},
+        solution::Solution,
+        solve_context::SolveContext,
+    },
+    utils::InputUtils,
+};
 
 pub struct Day4 {}
 
+register_challenge!(AdventOfCode2020, Day4);
+
 impl Day4 {
+    pub const TITLE: &'static str = "Day 4: Passport Processing";
+    pub const ID: &'static str = "day-4-passport-processing";
+
     fn count_valid(&self, passports: Vec<HashMap<&str, &str>>, ok_if_present: bool) -> usize {
+        lazy_static! {
+            static ref HCL: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
+            static ref PID: Regex = Regex::new(r"^\d{9}$").unwrap();
+        }
         let mut valid = 0;
         for passport in passports {
             let mut is_valid = true;
@@ -19,46 +36,40 @@ impl Day4 {
             if !ok_if_present && is_valid {
                 // byr
                 match passport.get("byr").unwrap().parse::<usize>() {
-                    Ok(byr) => is_valid = is_valid && byr >= 1920 && byr <= 2002,
+                    Ok(byr) => is_valid = is_valid && (1920..=2002).contains(&byr),
                     Err(_) => is_valid = false,
                 }
                 // iyr
                 match passport.get("iyr").unwrap().parse::<usize>() {
-                    Ok(iyr) => is_valid = is_valid && iyr >= 2010 && iyr <= 2020,
+                    Ok(iyr) => is_valid = is_valid && (2010..=2020).contains(&iyr),
                     Err(_) => is_valid = false,
                 }
                 // eyr
                 match passport.get("eyr").unwrap().parse::<usize>() {
-                    Ok(eyr) => is_valid = is_valid && eyr >= 2020 && eyr <= 2030,
+                    Ok(eyr) => is_valid = is_valid && (2020..=2030).contains(&eyr),
                     Err(_) => is_valid = false,
                 }
                 // hgt
                 let hgt_field = passport.get("hgt").unwrap();
                 if hgt_field.contains("cm") {
                     match hgt_field.replace("cm", "").parse::<usize>() {
-                        Ok(hgt) => is_valid = is_valid && hgt >= 150 && hgt <= 193,
+                        Ok(hgt) => is_valid = is_valid && (150..=193).contains(&hgt),
                         Err(_) => is_valid = false,
                     }
                 } else {
                     match hgt_field.replace("in", "").parse::<usize>() {
-                        Ok(hgt) => is_valid = is_valid && hgt >= 59 && hgt <= 76,
+                        Ok(hgt) => is_valid = is_valid && (59..=76).contains(&hgt),
                         Err(_) => is_valid = false,
                     }
                 }
                 // hcl
-                is_valid = is_valid
-                    && Regex::new(r"^#[0-9a-f]{6}$")
-                        .unwrap()
-                        .is_match(passport.get("hcl").unwrap());
+                is_valid = is_valid && HCL.is_match(passport.get("hcl").unwrap());
                 // ecl
                 is_valid = is_valid
-                    && (&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])
+                    && ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
                         .contains(passport.get("ecl").unwrap());
                 // pid
-                is_valid = is_valid
-                    && Regex::new(r"^\d{9}$")
-                        .unwrap()
-                        .is_match(passport.get("pid").unwrap());
+                is_valid = is_valid && PID.is_match(passport.get("pid").unwrap());
             }
 
             if is_valid {
@@ -71,25 +82,33 @@ impl Day4 {
 
 impl ChallengeConfig for Day4 {
     fn title(&self) -> &str {
-        return "Day 4: Passport Processing";
+        return Self::TITLE;
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["parsing", "validation"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let blocks = input.split_sections();
         let passports: Vec<HashMap<&str, &str>> = blocks
             .iter()
             .map(|x| {
                 x.split_whitespace()
-                    .into_iter()
                     .map(|f| (f.split(":").nth(0).unwrap(), f.split(":").nth(1).unwrap()))
                     .collect()
             })
             .collect();
 
-        let part_one = self.count_valid(passports.clone(), true);
-        let part_two = self.count_valid(passports.clone(), false);
-
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return match part {
+            1 => Ok(Solution::for_part(part, self.count_valid(passports, true))),
+            2 => Ok(Solution::for_part(part, self.count_valid(passports, false))),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -154,6 +173,11 @@ mod tests {
     )]
     fn solve(passports: &str, expected: &str) {
         let day = Day4 {};
-        assert_eq!(day.solve(passports).unwrap(), expected);
+        assert_eq!(
+            day.solve(passports, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
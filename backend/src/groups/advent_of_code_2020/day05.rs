@@ -1,7 +1,12 @@
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 fn slice_max_down(min: usize, max: usize) -> usize {
     return max - (max + 1 - min) / 2;
@@ -49,15 +54,8 @@ impl BoardingPass {
         return Ok(min);
     }
     fn get_seat_id(&self) -> Result<usize, ChallengeError> {
-        let col = match self.get_col() {
-            Ok(col) => col,
-            Err(e) => return Err(e),
-        };
-
-        let row = match self.get_row() {
-            Ok(row) => row,
-            Err(e) => return Err(e),
-        };
+        let col = self.get_col()?;
+        let row = self.get_row()?;
 
         Ok((row * 8) + col)
     }
@@ -65,14 +63,27 @@ impl BoardingPass {
 
 pub struct Day5 {}
 
-impl Day5 {}
+register_challenge!(AdventOfCode2020, Day5);
+
+impl Day5 {
+    pub const TITLE: &'static str = "Day 5: Binary Boarding";
+    pub const ID: &'static str = "day-5-binary-boarding";
+}
 
 impl ChallengeConfig for Day5 {
     fn title(&self) -> &str {
-        return "Day 5: Binary Boarding";
+        return Self::TITLE;
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["binary", "bit manipulation"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let passes: Vec<BoardingPass> = input
             .replace("  ", "")
             .split_whitespace()
@@ -85,9 +96,14 @@ impl ChallengeConfig for Day5 {
 
         let part_one_results: Result<Vec<usize>, _> =
             passes.iter().map(|p| p.get_seat_id()).collect();
-        let taken_seats: Vec<usize> = part_one_results?.iter().map(|x| *x).sorted().collect();
+        let taken_seats: Vec<usize> = part_one_results?.iter().copied().sorted().collect();
 
-        let part_one = taken_seats.iter().max().unwrap_or(&(0 as usize));
+        if part == 1 {
+            let part_one = taken_seats.iter().max().unwrap_or(&0);
+            return Ok(Solution::for_part(part, *part_one));
+        } else if part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
         let mut part_two: usize = 0;
         let mut last_taken: usize = 0;
@@ -103,7 +119,7 @@ impl ChallengeConfig for Day5 {
             return Err(ChallengeError::new("Unable to find an answer for part two").into());
         }
 
-        return Ok(format!("Part 1: {}\nPart 2: {:?}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, part_two));
     }
 }
 
@@ -142,6 +158,11 @@ mod tests {
     )]
     fn solve(passes: &str, expected: &str) {
         let day = Day5 {};
-        assert_eq!(day.solve(passes).unwrap(), expected);
+        assert_eq!(
+            day.solve(passes, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -1,36 +1,58 @@
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::{groups::challenge_config::ChallengeConfig, utils::InputUtils};
+use super::config::AdventOfCode2020;
+use crate::{
+    groups::{
+        challenge_config::{ChallengeConfig, ChallengeError},
+        solution::Solution,
+        solve_context::SolveContext,
+    },
+    utils::InputUtils,
+};
 
 pub struct Day6 {}
 
-impl Day6 {}
+register_challenge!(AdventOfCode2020, Day6);
+
+impl Day6 {
+    pub const TITLE: &'static str = "Day 6: Custom Customs";
+    pub const ID: &'static str = "day-6-custom-customs";
+}
 
 impl ChallengeConfig for Day6 {
     fn title(&self) -> &str {
-        return "Day 6: Custom Customs";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn tags(&self) -> Vec<&str> {
+        return vec!["parsing", "sets"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let groups = input.split_sections();
 
         // Part 1: count number of distinct letters in each group
         // Part 2: count number of distinct letters that are in each line in each group
-        let mut part_one = 0;
-        let mut part_two = 0;
+        if part != 1 && part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
+        let mut result = 0;
         for group in groups {
             let people_count = group.matches('\n').count() + 1;
             let distinct_letters = group.chars().filter(|x| x.is_alphabetic()).unique();
             for letter in distinct_letters {
-                part_one += 1;
-                if group.matches(letter).count() == people_count {
-                    part_two += 1;
+                if part == 1 || group.matches(letter).count() == people_count {
+                    result += 1;
                 }
             }
         }
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, result));
     }
 }
 
@@ -64,6 +86,11 @@ mod tests {
     )]
     fn solve(answers: &str, expected: &str) {
         let day = Day6 {};
-        assert_eq!(day.solve(answers).unwrap(), expected);
+        assert_eq!(
+            day.solve(answers, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -3,11 +3,21 @@ use std::collections::{HashMap, HashSet};
 use anyhow::Result;
 use regex::Regex;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day7 {}
 
+register_challenge!(AdventOfCode2020, Day7);
+
 impl Day7 {
+    pub const TITLE: &'static str = "Day 7: Handy Haversacks";
+    pub const ID: &'static str = "day-7-handy-haversacks";
+
     fn parse_rules(
         &self,
         input: &str,
@@ -24,14 +34,14 @@ impl Day7 {
             }
             let mut bag_name = "";
             for caps in BAG_RE.captures_iter(item) {
-                if bag_name != "" {
+                if !bag_name.is_empty() {
                     return Err(ChallengeError::new(
                         format!("Found two bag names in {}", item).as_str(),
                     ));
                 }
                 bag_name = caps.get(1).unwrap().as_str();
             }
-            if bag_name == "" {
+            if bag_name.is_empty() {
                 return Err(ChallengeError::new(
                     format!("Can't find bag name in {}", item).as_str(),
                 ));
@@ -61,7 +71,7 @@ impl Day7 {
         // This code is not efficient, the maps should be reversed beforehand for performance
         let mut all_colors: HashSet<&str> = HashSet::new();
         let mut new_items: HashSet<&str> = [bag].iter().cloned().collect();
-        while new_items.len() > 0 {
+        while !new_items.is_empty() {
             let mut container_bags: HashSet<&str> = HashSet::new();
             for new_item in new_items {
                 container_bags.extend(
@@ -82,8 +92,8 @@ impl Day7 {
     fn solve_part_two(&self, rules: HashMap<String, HashMap<String, usize>>, bag: &str) -> usize {
         // Part 2: Calculate the total number of bags a color will contain, this one will be more efficient
         let mut total_count = 0;
-        let mut new_items: Vec<String> = [bag.to_owned()].iter().cloned().collect();
-        while new_items.len() > 0 {
+        let mut new_items: Vec<String> = [bag.to_owned()].to_vec();
+        while !new_items.is_empty() {
             let mut contained_bags: Vec<String> = vec![];
             for new_item in new_items {
                 for (contained_bag, count) in rules[&new_item].clone() {
@@ -99,15 +109,30 @@ impl Day7 {
 
 impl ChallengeConfig for Day7 {
     fn title(&self) -> &str {
-        return "Day 7: Handy Haversacks";
+        return Self::TITLE;
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
-        let rules = self.parse_rules(input)?;
-        let part_one = self.solve_part_one(rules.clone(), "shiny gold bag");
-        let part_two = self.solve_part_two(rules.clone(), "shiny gold bag");
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+    fn tags(&self) -> Vec<&str> {
+        return vec!["graph", "recursion", "parsing"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
+        let rules = self.parse_rules(input)?;
+        return match part {
+            1 => Ok(Solution::for_part(
+                part,
+                self.solve_part_one(rules, "shiny gold bag"),
+            )),
+            2 => Ok(Solution::for_part(
+                part,
+                self.solve_part_two(rules, "shiny gold bag"),
+            )),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -145,6 +170,11 @@ mod tests {
     )]
     fn solve(rules: &str, expected: &str) {
         let day = Day7 {};
-        assert_eq!(day.solve(rules).unwrap(), expected);
+        assert_eq!(
+            day.solve(rules, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
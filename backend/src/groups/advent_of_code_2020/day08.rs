@@ -2,7 +2,12 @@ use std::{collections::HashSet, convert::TryInto};
 
 use anyhow::Result;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 struct Instruction {
     jump: isize,
@@ -49,25 +54,26 @@ impl Instruction {
 
 pub struct Day8 {}
 
+register_challenge!(AdventOfCode2020, Day8);
+
 impl Day8 {
+    pub const TITLE: &'static str = "Day 8: Handheld Halting";
+    pub const ID: &'static str = "day-8-handheld-halting";
+
     fn solve_part_one(&self, input: &str) -> Result<isize> {
-        let instructions: Vec<Instruction> = match input
+        let instructions: Vec<Instruction> = input
             .split("\n")
             .map(|x| x.trim())
             .filter(|x| !x.is_empty())
             .map(Instruction::parse)
-            .collect()
-        {
-            Ok(result) => result,
-            Err(e) => return Err(e),
-        };
+            .collect::<Result<_>>()?;
 
         let mut position: isize = 0;
         let mut acc: isize = 0;
         let mut run_positions: HashSet<isize> = HashSet::new();
         while !run_positions.contains(&position)
             && position >= 0
-            && position < instructions.len().try_into().unwrap()
+            && position < instructions.len() as isize
         {
             run_positions.insert(position);
             let position_u: usize = position.try_into().unwrap();
@@ -78,7 +84,7 @@ impl Day8 {
         return Ok(acc);
     }
 
-    fn solve_part_two(&self, input: &str) -> Result<isize> {
+    fn solve_part_two(&self, input: &str, context: &SolveContext) -> Result<isize> {
         let lines: Vec<String> = input
             .split("\n")
             .map(|x| String::from(x.trim()))
@@ -86,20 +92,20 @@ impl Day8 {
             .collect();
 
         for (i, line) in lines.iter().enumerate() {
+            context.check()?;
             let mut new_lines = lines.clone();
             if line.contains("nop") {
-                new_lines[i] = line.replace("jmp", "nop");
+                new_lines[i] = line.replace("nop", "jmp");
             } else if line.contains("jmp") {
                 new_lines[i] = line.replace("jmp", "nop");
             } else {
                 continue;
             }
 
-            let instructions: Vec<Instruction> =
-                match new_lines.iter().map(|x| Instruction::parse(x)).collect() {
-                    Ok(result) => result,
-                    Err(e) => return Err(e),
-                };
+            let instructions: Vec<Instruction> = new_lines
+                .iter()
+                .map(|x| Instruction::parse(x))
+                .collect::<Result<_>>()?;
             let max_len: isize = instructions.len().try_into().unwrap();
 
             let mut position: isize = 0;
@@ -123,20 +129,26 @@ impl Day8 {
 
 impl ChallengeConfig for Day8 {
     fn title(&self) -> &str {
-        return "Day 8: Handheld Halting";
+        return Self::TITLE;
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
-        let part_one = match self.solve_part_one(input.clone()) {
-            Ok(result) => result,
-            Err(e) => return Err(e.downcast().unwrap()),
-        };
-        let part_two = match self.solve_part_two(input.clone()) {
-            Ok(result) => result,
-            Err(e) => return Err(e.downcast().unwrap()),
-        };
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["virtual machine", "simulation"];
+    }
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
+        return match part {
+            1 => Ok(Solution::for_part(part, self.solve_part_one(input)?)),
+            2 => Ok(Solution::for_part(
+                part,
+                self.solve_part_two(input, context)?,
+            )),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -160,10 +172,23 @@ mod tests {
             jmp -4
             acc +6",
             "Part 1: 5\nPart 2: 8"
+        ),
+        case(
+            "nop +4
+            acc +1
+            jmp -2
+            jmp -1
+            acc +5",
+            "Part 1: 1\nPart 2: 5"
         )
     )]
     fn solve(instructions: &str, expected: &str) {
         let day = Day8 {};
-        assert_eq!(day.solve(instructions).unwrap(), expected);
+        assert_eq!(
+            day.solve(instructions, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -1,16 +1,36 @@
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day9 {}
 
+register_challenge!(AdventOfCode2020, Day9);
+
+impl Day9 {
+    pub const TITLE: &'static str = "Day 9: Encoding Error";
+    pub const ID: &'static str = "day-9-encoding-error";
+}
+
 impl ChallengeConfig for Day9 {
     fn title(&self) -> &str {
-        return "Day 9: Encoding Error";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["search", "sliding window"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let numbers: Vec<usize> = input
             .split_whitespace()
             .map(|x| x.trim())
@@ -49,12 +69,16 @@ impl ChallengeConfig for Day9 {
                 break;
             }
         }
+        if part == 1 {
+            return Ok(Solution::for_part(part, part_one));
+        } else if part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
         // Part 2: Find contiguous sequence that sums to the invalid number
         let mut part_two = 0;
         let mut active_sequences: Vec<Vec<usize>> = vec![];
-        for i in 0..numbers.len() - 1 {
-            let new_number = numbers[i];
+        for &new_number in &numbers[..numbers.len() - 1] {
             let mut indices_to_remove: Vec<usize> = vec![];
             for (s_index, sequence) in active_sequences.clone().iter().enumerate() {
                 let mut new_sequence = sequence.clone();
@@ -66,7 +90,7 @@ impl ChallengeConfig for Day9 {
                     break;
                 } else if sum < part_one {
                     // Replace the sequence, we're keeping it
-                    new_sequence.push(new_number.clone());
+                    new_sequence.push(new_number);
                     active_sequences[s_index] = new_sequence.clone();
                 } else if sum > part_one {
                     indices_to_remove.push(s_index);
@@ -83,7 +107,7 @@ impl ChallengeConfig for Day9 {
             active_sequences.push(vec![new_number]);
         }
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, part_two));
     }
 }
 
@@ -122,6 +146,11 @@ mod tests {
     )]
     fn solve(numbers: &str, expected: &str) {
         let day = Day9 {};
-        assert_eq!(day.solve(numbers).unwrap(), expected);
+        assert_eq!(
+            day.solve(numbers, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -6,16 +6,39 @@ use petgraph::{
     graphmap::DiGraphMap,
 };
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day10 {}
 
+register_challenge!(AdventOfCode2020, Day10);
+
+impl Day10 {
+    pub const TITLE: &'static str = "Day 10: Adapter Array";
+    pub const ID: &'static str = "day-10-adapter-array";
+}
+
 impl ChallengeConfig for Day10 {
     fn title(&self) -> &str {
-        return "Day 10: Adapter Array";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn tags(&self) -> Vec<&str> {
+        return vec!["dynamic programming", "combinatorics"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
+        if part != 1 && part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
         let mut numbers: Vec<usize> = input
             .split_whitespace()
             .map(|x| x.trim())
@@ -52,8 +75,11 @@ impl ChallengeConfig for Day10 {
                 1 => jump1 += 1,
                 3 => {
                     jump3 += 1;
-                    part_two = part_two
-                        * all_simple_paths::<Vec<usize>, _>(&graph, previous, node_map[i], 1, None)
+                    if part == 1 {
+                        continue;
+                    }
+                    part_two *=
+                        all_simple_paths::<Vec<usize>, _>(&graph, previous, node_map[i], 1, None)
                             .count()
                             .max(1);
                     previous = node_map[i];
@@ -62,9 +88,10 @@ impl ChallengeConfig for Day10 {
             }
         }
 
-        let part_one = jump1 * jump3;
-
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        if part == 1 {
+            return Ok(Solution::for_part(part, jump1 * jump3));
+        }
+        return Ok(Solution::for_part(part, part_two));
     }
 }
 
@@ -128,6 +155,11 @@ mod tests {
     )]
     fn solve(adapters: &str, expected: &str) {
         let day = Day10 {};
-        assert_eq!(day.solve(adapters).unwrap(), expected);
+        assert_eq!(
+            day.solve(adapters, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
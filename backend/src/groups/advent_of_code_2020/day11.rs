@@ -1,7 +1,12 @@
 use anyhow::Result;
 use ndarray::{Array, Array2, Axis};
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 const FLOOR: usize = 0;
 const FREE: usize = 1;
@@ -9,7 +14,12 @@ const OCCUPIED: usize = 2;
 
 pub struct Day11 {}
 
+register_challenge!(AdventOfCode2020, Day11);
+
 impl Day11 {
+    pub const TITLE: &'static str = "Day 11: Seating System";
+    pub const ID: &'static str = "day-11-seating-system";
+
     fn adjacent(array: &Array2<usize>, row: usize, col: usize) -> usize {
         let mut adj_count = 0;
         let row_count = array.len_of(Axis(0));
@@ -67,10 +77,18 @@ impl Day11 {
 
 impl ChallengeConfig for Day11 {
     fn title(&self) -> &str {
-        return "Day 11: Seating System";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["grid", "cellular automaton", "simulation"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
         let lines: Vec<&str> = input
             .split("\n")
             .map(|x| x.trim())
@@ -92,51 +110,46 @@ impl ChallengeConfig for Day11 {
             ));
         }
 
-        // Part one
+        // Part 1: a seat is freed when 4 adjacent seats are occupied
+        // Part 2: a seat is freed when 5 seen seats are occupied
+        let max_occupied = match part {
+            1 => 4,
+            2 => 5,
+            _ => return Err(ChallengeError::invalid_part(part).into()),
+        };
+        // Progress is estimated by the share of seats that didn't change during the last round
+        let seat_count = array.iter().filter(|x| **x != FLOOR).count();
         let mut changed = true;
-        let mut part_one_array = array.clone();
-        while changed {
-            let cloned_array = part_one_array.clone();
-            changed = false;
-            for ((row, col), value) in part_one_array.indexed_iter_mut() {
-                if value == &FLOOR {
-                    continue;
-                }
-                let adjacent_count = Day11::adjacent(&cloned_array, row, col);
-                if *value == FREE && adjacent_count == 0 {
-                    *value = OCCUPIED;
-                    changed = true;
-                } else if *value == OCCUPIED && adjacent_count >= 4 {
-                    *value = FREE;
-                    changed = true;
-                }
-            }
-        }
-        let part_one: usize = part_one_array.iter().filter(|x| **x == OCCUPIED).count();
-
-        // Part two
-        changed = true;
-        let mut part_two_array = array.clone();
         while changed {
-            let cloned_array = part_two_array.clone();
+            context.check()?;
+            let cloned_array = array.clone();
             changed = false;
-            for ((row, col), value) in part_two_array.indexed_iter_mut() {
+            let mut changed_count = 0;
+            for ((row, col), value) in array.indexed_iter_mut() {
                 if value == &FLOOR {
                     continue;
                 }
-                let seen_count = Day11::seen(&cloned_array, row as isize, col as isize);
-                if *value == FREE && seen_count == 0 {
+                let occupied_count = match part {
+                    1 => Day11::adjacent(&cloned_array, row, col),
+                    _ => Day11::seen(&cloned_array, row as isize, col as isize),
+                };
+                if *value == FREE && occupied_count == 0 {
                     *value = OCCUPIED;
                     changed = true;
-                } else if *value == OCCUPIED && seen_count >= 5 {
+                    changed_count += 1;
+                } else if *value == OCCUPIED && occupied_count >= max_occupied {
                     *value = FREE;
                     changed = true;
+                    changed_count += 1;
                 }
             }
+            context
+                .progress()
+                .set_steps(seat_count - changed_count, seat_count);
         }
-        let part_two: usize = part_two_array.iter().filter(|x| **x == OCCUPIED).count();
+        let result: usize = array.iter().filter(|x| **x == OCCUPIED).count();
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, result));
     }
 }
 
@@ -165,6 +178,11 @@ mod tests {
     )]
     fn solve(seats: &str, expected: &str) {
         let day = Day11 {};
-        assert_eq!(day.solve(seats).unwrap(), expected);
+        assert_eq!(
+            day.solve(seats, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
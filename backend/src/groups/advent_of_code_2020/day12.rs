@@ -1,10 +1,20 @@
 use anyhow::Result;
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day12 {}
 
+register_challenge!(AdventOfCode2020, Day12);
+
 impl Day12 {
+    pub const TITLE: &'static str = "Day 12: Rain Risk";
+    pub const ID: &'static str = "day-12-rain-risk";
+
     fn solve_part_one(&self, moves: Vec<(char, isize)>) -> isize {
         let possible_directions = [(0, 1), (-1, 0), (0, -1), (1, 0)];
         let mut direction_index = 1000000; // To avoid doing arithmetic (other than mod)
@@ -29,7 +39,7 @@ impl Day12 {
     }
 
     fn rotate_coords(coords: (isize, isize), clockwise: bool, times: isize) -> (isize, isize) {
-        let mut new_coords = coords.clone();
+        let mut new_coords = coords;
         for _ in 0..times {
             if clockwise {
                 new_coords = (-new_coords.1, new_coords.0);
@@ -64,10 +74,18 @@ impl Day12 {
 
 impl ChallengeConfig for Day12 {
     fn title(&self) -> &str {
-        return "Day 12: Rain Risk";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn tags(&self) -> Vec<&str> {
+        return vec!["geometry", "navigation", "simulation"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let moves: Vec<(char, isize)> = input
             .split_whitespace()
             .map(|x| x.trim())
@@ -80,10 +98,11 @@ impl ChallengeConfig for Day12 {
             })
             .collect();
 
-        let part_one = self.solve_part_one(moves.clone());
-        let part_two = self.solve_part_two(moves.clone());
-
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return match part {
+            1 => Ok(Solution::for_part(part, self.solve_part_one(moves))),
+            2 => Ok(Solution::for_part(part, self.solve_part_two(moves))),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -107,6 +126,11 @@ mod tests {
     )]
     fn solve(moves: &str, expected: &str) {
         let day = Day12 {};
-        assert_eq!(day.solve(moves).unwrap(), expected);
+        assert_eq!(
+            day.solve(moves, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
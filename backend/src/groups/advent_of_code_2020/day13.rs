@@ -2,11 +2,21 @@ use anyhow::Result;
 use modinverse::egcd;
 use num_integer::Integer;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day13 {}
 
+register_challenge!(AdventOfCode2020, Day13);
+
 impl Day13 {
+    pub const TITLE: &'static str = "Day 13: Shuttle Search";
+    pub const ID: &'static str = "day-13-shuttle-search";
+
     fn align_phases(a_period: i128, a_phase: i128, b_period: i128, b_phase: i128) -> (i128, i128) {
         let (gcd, s, _t) = egcd(a_period, b_period);
         let phase_difference = a_phase - b_phase;
@@ -14,46 +24,26 @@ impl Day13 {
         if pd_remainder != 0 {
             panic!("Reference points never synchronize.");
         }
-        let combined_period = a_period.div_floor(&(gcd)) * b_period;
+        let combined_period = Integer::div_floor(&a_period, &gcd) * b_period;
         let combined_phase = (a_phase - s * pd_mult * a_period) % combined_period;
         return (combined_period, combined_phase);
     }
-}
-
-impl ChallengeConfig for Day13 {
-    fn title(&self) -> &str {
-        return "Day 13: Shuttle Search";
-    }
-
-    fn solve(&self, input: &str) -> Result<String> {
-        let (first, second) = input
-            .split_once("\n")
-            .ok_or(ChallengeError::new("Unable to split into two parts"))?;
-        let earliest_time = first.parse::<i128>()?;
-        let buses: Vec<i128> = second
-            .split(",")
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .map(|x| {
-                if x == "x" {
-                    return "0";
-                }
-                return x;
-            })
-            .map(str::parse::<i128>)
-            .collect::<Result<_, _>>()?;
 
+    fn solve_part_one(earliest_time: i128, buses: &[i128]) -> i128 {
         let mut part_one = 0;
-        let mut time = earliest_time.clone();
+        let mut time = earliest_time;
         while part_one == 0 {
-            for bus in buses.clone().iter().filter(|x| **x > 0) {
+            for bus in buses.iter().filter(|x| **x > 0) {
                 if time % bus == 0 {
                     part_one = bus * (time - earliest_time);
                 }
             }
             time += 1;
         }
+        return part_one;
+    }
 
+    fn solve_part_two(buses: &[i128]) -> i128 {
         let mut part_two = 0;
         let mut lcm = 1;
         for (i, bus) in buses.iter().enumerate() {
@@ -67,8 +57,49 @@ impl ChallengeConfig for Day13 {
             part_two = phase;
             lcm = period;
         }
+        return part_two;
+    }
+}
+
+impl ChallengeConfig for Day13 {
+    fn title(&self) -> &str {
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["number theory", "chinese remainder theorem"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
+        let (first, second) = input
+            .split_once("\n")
+            .ok_or(ChallengeError::new("Unable to split into two parts"))?;
+        let earliest_time = first.parse::<i128>()?;
+        let buses: Vec<i128> = second
+            .split(",")
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(|x| {
+                if x == "x" {
+                    return "0";
+                }
+                return x;
+            })
+            .map(str::parse::<i128>)
+            .collect::<Result<_, _>>()?;
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return match part {
+            1 => Ok(Solution::for_part(
+                part,
+                Self::solve_part_one(earliest_time, &buses),
+            )),
+            2 => Ok(Solution::for_part(part, Self::solve_part_two(&buses))),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -93,6 +124,11 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day = Day13 {};
-        assert_eq!(day.solve(input).unwrap(), expected);
+        assert_eq!(
+            day.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
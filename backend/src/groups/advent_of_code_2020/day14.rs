@@ -3,69 +3,104 @@ use std::collections::HashMap;
 use anyhow::Result;
 use regex::Regex;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day14 {}
 
-impl Day14 {}
+register_challenge!(AdventOfCode2020, Day14);
+
+impl Day14 {
+    pub const TITLE: &'static str = "Day 14: Docking Data";
+    pub const ID: &'static str = "day-14-docking-data";
+
+    fn mask_value(mask: &str, value: usize) -> usize {
+        let mut masked_value = value;
+        for (i, c) in mask.chars().rev().enumerate() {
+            let mask_value = 2_usize.pow(i as u32);
+            match c {
+                '1' => masked_value |= mask_value,
+                '0' => masked_value &= 68719476735 - mask_value,
+                _ => {}
+            }
+        }
+        return masked_value;
+    }
+
+    fn mask_address(mask: &str, address: usize) -> Vec<usize> {
+        let mut addresses: Vec<usize> = vec![address];
+        for (i, c) in mask.chars().rev().enumerate() {
+            let mask_value = 2_usize.pow(i as u32);
+            match c {
+                '1' => {
+                    for (i, address) in addresses.clone().iter().enumerate() {
+                        addresses[i] = address | mask_value;
+                    }
+                }
+                'X' => {
+                    let addresses_to_double = addresses.clone();
+                    addresses.clear();
+                    for address in addresses_to_double.iter() {
+                        addresses.push(address | mask_value);
+                        addresses.push(address & (68719476735 - mask_value));
+                    }
+                }
+                _ => {}
+            }
+        }
+        return addresses;
+    }
+}
 
 impl ChallengeConfig for Day14 {
     fn title(&self) -> &str {
-        return "Day 14: Docking Data";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["bit manipulation", "parsing"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let instructions: Vec<&str> = input
             .split("\n")
             .map(str::trim)
             .filter(|x| !x.is_empty())
             .collect();
 
+        if part != 1 && part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
+
         let mut mask = "X".repeat(36);
-        let mut part_one_values: HashMap<usize, usize> = HashMap::new();
-        let mut part_two_values: HashMap<usize, usize> = HashMap::new();
+        let mut values: HashMap<usize, usize> = HashMap::new();
         for instruction in instructions {
             lazy_static! {
                 static ref MASK_REGEX: Regex = Regex::new(r"^mask = ([X01]{36})$").unwrap();
                 static ref ASSIGN_REGEX: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
             }
-            let mask_captures = MASK_REGEX.captures(instruction);
-            let assign_captures = ASSIGN_REGEX.captures(instruction);
-            if mask_captures.iter().count() > 0 {
-                for caps in mask_captures {
-                    mask = caps.get(1).unwrap().as_str().to_string();
-                }
-            } else if assign_captures.iter().count() > 0 {
-                for caps in assign_captures {
-                    let address = caps.get(1).unwrap().as_str().parse::<usize>()?;
-                    let value = caps.get(2).unwrap().as_str().parse::<usize>()?;
-
-                    let mut part_one_value = value.clone();
-                    let mut part_two_addresses: Vec<usize> = vec![address.clone()];
-                    for (i, c) in mask.chars().rev().enumerate() {
-                        let mask_value = (2 as usize).pow(i as u32);
-                        match c {
-                            '1' => {
-                                part_one_value = part_one_value | mask_value;
-                                for (i, address) in part_two_addresses.clone().iter().enumerate() {
-                                    part_two_addresses[i] = address | mask_value;
-                                }
-                            }
-                            '0' => part_one_value = part_one_value & (68719476735 - mask_value),
-                            'X' => {
-                                let addresses_to_double = part_two_addresses.clone();
-                                part_two_addresses.clear();
-                                for address in addresses_to_double.iter() {
-                                    part_two_addresses.push(address | mask_value);
-                                    part_two_addresses.push(address & (68719476735 - mask_value));
-                                }
-                            }
-                            _ => {}
-                        }
+            if let Some(caps) = MASK_REGEX.captures(instruction) {
+                mask = caps.get(1).unwrap().as_str().to_string();
+            } else if let Some(caps) = ASSIGN_REGEX.captures(instruction) {
+                let address = caps.get(1).unwrap().as_str().parse::<usize>()?;
+                let value = caps.get(2).unwrap().as_str().parse::<usize>()?;
+
+                match part {
+                    1 => {
+                        values.insert(address, Self::mask_value(&mask, value));
                     }
-                    part_one_values.insert(address, part_one_value);
-                    for address in part_two_addresses {
-                        part_two_values.insert(address, value);
+                    _ => {
+                        for address in Self::mask_address(&mask, address) {
+                            values.insert(address, value);
+                        }
                     }
                 }
             } else {
@@ -75,10 +110,9 @@ impl ChallengeConfig for Day14 {
                 .into());
             }
         }
-        let part_one: usize = part_one_values.values().sum();
-        let part_two: usize = part_two_values.values().sum();
+        let result: usize = values.values().sum();
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, result));
     }
 }
 
@@ -108,6 +142,11 @@ mod tests {
     )]
     fn solve(instructions: &str, expected: &str) {
         let day = Day14 {};
-        assert_eq!(day.solve(instructions).unwrap(), expected);
+        assert_eq!(
+            day.solve(instructions, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -1,17 +1,38 @@
 use anyhow::Result;
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
+
+/// Number of turns between two cancellation checks
+const CHECK_INTERVAL: usize = 1 << 20;
 
 pub struct Day15 {}
 
-impl Day15 {}
+register_challenge!(AdventOfCode2020, Day15);
+
+impl Day15 {
+    pub const TITLE: &'static str = "Day 15: Rambunctious Recitation";
+    pub const ID: &'static str = "day-15-rambunctious-recitation";
+}
 
 impl ChallengeConfig for Day15 {
     fn title(&self) -> &str {
-        return "Day 15: Rambunctious Recitation";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["game", "simulation"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
         let numbers: Vec<usize> = input
             .split(",")
             .map(str::trim)
@@ -19,29 +40,39 @@ impl ChallengeConfig for Day15 {
             .map(str::parse::<usize>)
             .collect::<Result<_, _>>()?;
 
-        let mut results = vec![];
-        for (part, nth_number) in [("Part 1", 2020 as usize), ("Part 2", 30000000)].iter() {
-            let mut last_spoken: Vec<Option<usize>> = std::iter::repeat(None)
-                .take(*nth_number)
-                .collect::<Vec<_>>();
-            for i in 0..numbers.len() {
-                last_spoken[numbers[i]] = Some(i);
-            }
+        let nth_number: usize = match part {
+            1 => 2020,
+            2 => 30000000,
+            _ => return Err(ChallengeError::invalid_part(part).into()),
+        };
+        let mut last: usize = match numbers.last() {
+            Some(n) => *n,
+            None => return Err(ChallengeError::new("No starting numbers").into()),
+        };
+        if numbers.len() >= nth_number {
+            return Ok(Solution::for_part(part, numbers[nth_number - 1]));
+        }
 
-            let mut last: usize = *numbers.last().unwrap();
-            for i in numbers.len()..*nth_number {
-                let new_number = match last_spoken[last] {
-                    Some(index) => i - index - 1,
-                    _ => 0,
-                };
-                last_spoken[last] = Some(i - 1);
-                last = new_number;
-            }
+        let max_number = numbers.iter().max().unwrap();
+        let mut last_spoken: Vec<Option<usize>> = vec![None; nth_number.max(max_number + 1)];
+        for i in 0..numbers.len() {
+            last_spoken[numbers[i]] = Some(i);
+        }
 
-            results.push(format!("{}: {}", part, last).to_owned());
+        for i in numbers.len()..nth_number {
+            if i % CHECK_INTERVAL == 0 {
+                context.check()?;
+                context.progress().set_steps(i, nth_number);
+            }
+            let new_number = match last_spoken[last] {
+                Some(index) => i - index - 1,
+                _ => 0,
+            };
+            last_spoken[last] = Some(i - 1);
+            last = new_number;
         }
 
-        return Ok(results.join("\n"));
+        return Ok(Solution::for_part(part, last));
     }
 }
 
@@ -50,6 +81,7 @@ mod tests {
     use rstest::rstest;
 
     use super::*;
+    use crate::groups::challenge_config::ChallengeErrorKind;
 
     #[rstest(
         input,
@@ -63,6 +95,63 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day = Day15 {};
-        assert_eq!(day.solve(input).unwrap(), expected);
+        assert_eq!(
+            day.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
+    }
+
+    #[rstest(
+        input,
+        expected,
+        case("0,3,6", "Part 1: 436"),
+        case("3,1,2", "Part 1: 1836"),
+        case("5000,3", "Part 1: 5"),
+        case("3,5000", "Part 1: 18")
+    )]
+    fn solve_part_one(input: &str, expected: &str) {
+        let day = Day15 {};
+        assert_eq!(
+            day.solve_part(input, 1, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
+    }
+
+    #[test]
+    fn no_starting_numbers() {
+        let day = Day15 {};
+        assert_eq!(
+            day.solve_part(" ", 1, &SolveContext::default())
+                .unwrap_err()
+                .to_string(),
+            "No starting numbers"
+        );
+    }
+
+    #[test]
+    fn invalid_part() {
+        let day = Day15 {};
+        assert_eq!(
+            day.solve_part("0,3,6", 3, &SolveContext::default())
+                .unwrap_err()
+                .to_string(),
+            "Part 3 does not exist"
+        );
+    }
+
+    #[test]
+    fn cancelled() {
+        let day = Day15 {};
+        let context = SolveContext::default();
+        context.token().cancel();
+        let error = day.solve_part("0,3,6", 2, &context).unwrap_err();
+        assert_eq!(
+            error.downcast::<ChallengeError>().unwrap().kind(),
+            ChallengeErrorKind::Cancelled
+        );
     }
 }
@@ -2,11 +2,23 @@ use std::collections::HashSet;
 
 use anyhow::Result;
 
+use super::config::AdventOfCode2020;
 use crate::{
-    groups::challenge_config::{ChallengeConfig, ChallengeError},
+    groups::{
+        challenge_config::{ChallengeConfig, ChallengeError},
+        solution::Solution,
+        solve_context::SolveContext,
+    },
     utils::InputUtils,
 };
 pub struct Day16 {}
+
+register_challenge!(AdventOfCode2020, Day16);
+
+impl Day16 {
+    pub const TITLE: &'static str = "Day 16: Ticket Translation";
+    pub const ID: &'static str = "day-16-ticket-translation";
+}
 struct Rule {
     name: String,
     conditions: Vec<(usize, usize)>,
@@ -52,10 +64,18 @@ impl Rule {
 
 impl ChallengeConfig for Day16 {
     fn title(&self) -> &str {
-        return "Day 16: Ticket Translation";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["parsing", "validation", "constraint solving"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let groups = input
             .replace("nearby tickets:\n", "")
             .replace("your ticket:\n", "")
@@ -103,6 +123,11 @@ impl ChallengeConfig for Day16 {
             }
             return ticket_ok;
         });
+        if part == 1 {
+            return Ok(Solution::for_part(part, part_one));
+        } else if part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
         // Part two: Match fields with own ticket. Loop until all rules have only one possible field left
         let mut remaining_columns: HashSet<usize> = (0..rules.len()).collect();
@@ -140,7 +165,7 @@ impl ChallengeConfig for Day16 {
             .product::<Option<usize>>()
             .unwrap();
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, part_two));
     }
 }
 
@@ -171,6 +196,11 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day = Day16 {};
-        assert_eq!(day.solve(input).unwrap(), expected);
+        assert_eq!(
+            day.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -1,11 +1,21 @@
 use anyhow::Result;
 use ndarray::{Array, Array2, Array3, Array4, Axis};
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day17 {}
 
+register_challenge!(AdventOfCode2020, Day17);
+
 impl Day17 {
+    pub const TITLE: &'static str = "Day 17: Conway Cubes";
+    pub const ID: &'static str = "day-17-conway-cubes";
+
     fn adjacent_3d(array: Array3<usize>, pos: (usize, usize, usize)) -> usize {
         let mut total = 0;
         for x in (pos.0 - 1)..=(pos.0 + 1) {
@@ -43,20 +53,33 @@ impl Day17 {
 
 impl ChallengeConfig for Day17 {
     fn title(&self) -> &str {
-        return "Day 17: Conway Cubes";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn tags(&self) -> Vec<&str> {
+        return vec!["grid", "cellular automaton", "simulation"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
         let lines: Vec<&str> = input
             .split("\n")
             .map(|x| x.trim())
             .filter(|x| !x.is_empty())
             .collect();
         let iterations = 6;
+        if part != 1 && part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
         let n = lines[0].len().max(lines.len()) + (iterations + 1) * 2;
         let mut array_3d: Array3<usize> = Array3::zeros((n, n, n));
-        let mut array_4d: Array4<usize> = Array4::zeros((n, n, n, n));
+        // The 4D space is only needed for part 2
+        let n_4d = if part == 2 { n } else { 0 };
+        let mut array_4d: Array4<usize> = Array4::zeros((n_4d, n_4d, n_4d, n_4d));
         let mut assigned_slice: Array2<usize> = Array2::zeros((n, n));
         for (i, mut row) in assigned_slice.axis_iter_mut(Axis(0)).enumerate() {
             if i <= iterations || i > iterations + lines.len() {
@@ -85,9 +108,34 @@ impl ChallengeConfig for Day17 {
         }
 
         for iteration in 1..=iterations {
+            context.check()?;
+            context.progress().set_steps(iteration - 1, iterations);
             // Limit the number of indices to check, the active states are only as far as the number of iterations
             let min_index = iterations - iteration;
             let max_index = n - (iterations - iteration) - 1;
+            if part == 2 {
+                let copied_array_4d = array_4d.clone();
+                for ((x, y, z, w), item) in array_4d.indexed_iter_mut() {
+                    if x <= min_index
+                        || y <= min_index
+                        || z <= min_index
+                        || w <= min_index
+                        || x >= max_index
+                        || y >= max_index
+                        || z >= max_index
+                        || w >= max_index
+                    {
+                        continue;
+                    }
+                    let adjacent = Self::adjacent_4d(copied_array_4d.clone(), (x, y, z, w));
+                    if *item == 1 && adjacent != 2 && adjacent != 3 {
+                        *item = 0;
+                    } else if *item == 0 && adjacent == 3 {
+                        *item = 1;
+                    }
+                }
+                continue;
+            }
             let copied_array_3d = array_3d.clone();
             for ((x, y, z), item) in array_3d.indexed_iter_mut() {
                 if x <= min_index
@@ -106,32 +154,14 @@ impl ChallengeConfig for Day17 {
                     *item = 1;
                 }
             }
-            let copied_array_4d = array_4d.clone();
-            for ((x, y, z, w), item) in array_4d.indexed_iter_mut() {
-                if x <= min_index
-                    || y <= min_index
-                    || z <= min_index
-                    || w <= min_index
-                    || x >= max_index
-                    || y >= max_index
-                    || z >= max_index
-                    || w >= max_index
-                {
-                    continue;
-                }
-                let adjacent = Self::adjacent_4d(copied_array_4d.clone(), (x, y, z, w));
-                if *item == 1 && adjacent != 2 && adjacent != 3 {
-                    *item = 0;
-                } else if *item == 0 && adjacent == 3 {
-                    *item = 1;
-                }
-            }
         }
 
-        let part_one: usize = array_3d.iter().filter(|x| **x == 1).count();
-        let part_two: usize = array_4d.iter().filter(|x| **x == 1).count();
+        let result: usize = match part {
+            1 => array_3d.iter().filter(|x| **x == 1).count(),
+            _ => array_4d.iter().filter(|x| **x == 1).count(),
+        };
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, result));
     }
 }
 
@@ -153,6 +183,11 @@ mod tests {
     )]
     fn solve(initial_layer: &str, expected: &str) {
         let day = Day17 {};
-        assert_eq!(day.solve(initial_layer).unwrap(), expected);
+        assert_eq!(
+            day.solve(initial_layer, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
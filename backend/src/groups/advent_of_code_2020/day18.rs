@@ -1,10 +1,20 @@
 use anyhow::Result;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day18 {}
 
+register_challenge!(AdventOfCode2020, Day18);
+
 impl Day18 {
+    pub const TITLE: &'static str = "Day 18: Operation Order";
+    pub const ID: &'static str = "day-18-operation-order";
+
     fn calculate(equation: &str, additions_first: bool) -> Result<usize> {
         let mut to_calc = equation.to_owned();
 
@@ -32,9 +42,10 @@ impl Day18 {
             }
             let split: Vec<String> = to_calc.split_whitespace().map(str::to_string).collect();
             let mut i = 0;
-            let plus_index = split.iter().position(|r| *r == "+".to_string());
-            if additions_first && plus_index.is_some() {
-                i = plus_index.unwrap() - 1;
+            if additions_first {
+                if let Some(plus_index) = split.iter().position(|r| r == "+") {
+                    i = plus_index - 1;
+                }
             }
             let first_number = split[i].parse::<usize>()?;
             let second_number = split[i + 2].parse::<usize>()?;
@@ -54,24 +65,36 @@ impl Day18 {
 
 impl ChallengeConfig for Day18 {
     fn title(&self) -> &str {
-        return "Day 18: Operation Order";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["parsing", "expression evaluation"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let lines: Vec<&str> = input
             .split("\n")
             .map(|x| x.trim())
             .filter(|x| !x.is_empty())
             .collect();
 
-        let mut part_one: usize = 0;
-        let mut part_two: usize = 0;
+        // Part 2: Addition is evaluated before multiplication
+        let additions_first = match part {
+            1 => false,
+            2 => true,
+            _ => return Err(ChallengeError::invalid_part(part).into()),
+        };
+        let mut result: usize = 0;
         for line in lines {
-            part_one += Self::calculate(line, false)?;
-            part_two += Self::calculate(line, true)?;
+            result += Self::calculate(line, additions_first)?;
         }
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, result));
     }
 }
 
@@ -98,6 +121,11 @@ mod tests {
     )]
     fn solve(homework: &str, expected: &str) {
         let day = Day18 {};
-        assert_eq!(day.solve(homework).unwrap(), expected);
+        assert_eq!(
+            day.solve(homework, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -6,19 +6,32 @@ use std::{
 use anyhow::Result;
 use lru::LruCache;
 
+use super::config::AdventOfCode2020;
 use crate::{
-    groups::challenge_config::{ChallengeConfig, ChallengeError},
+    groups::{
+        challenge_config::{ChallengeConfig, ChallengeError},
+        solution::Solution,
+        solve_context::SolveContext,
+    },
     utils::InputUtils,
 };
 
 pub struct Day19 {}
+
+register_challenge!(AdventOfCode2020, Day19);
+
+impl Day19 {
+    pub const TITLE: &'static str = "Day 19: Monster Messages";
+    pub const ID: &'static str = "day-19-monster-messages";
+}
 struct Rules {
     rules: HashMap<usize, String>,
     resolved_rules: LruCache<usize, HashSet<String>>,
+    context: SolveContext,
 }
 
 impl Rules {
-    fn new(rules: &str) -> Result<Self> {
+    fn new(rules: &str, context: &SolveContext) -> Result<Self> {
         return Ok(Rules {
             rules: rules
                 .split("\n")
@@ -27,6 +40,7 @@ impl Rules {
                 .map(Rules::rule_to_tuple)
                 .collect::<Result<_, _>>()?,
             resolved_rules: LruCache::new(rules.len()),
+            context: context.clone(),
         });
     }
 
@@ -51,6 +65,7 @@ impl Rules {
     }
 
     fn resolve(&mut self, rule: &str) -> Result<HashSet<String>> {
+        self.context.check()?;
         if rule.contains("\"") {
             let mut values: HashSet<String> = HashSet::new();
             values.insert(rule.split("\"").nth(1).unwrap().to_owned());
@@ -90,7 +105,7 @@ impl Rules {
     fn matches_forty_two_and_thirty_one(&mut self, message: &str) -> Result<bool> {
         let forty_two = self.resolve_id(42)?;
 
-        let mut message = message.clone();
+        let mut message = message;
         let thirty_one = self.resolve_id(31)?;
         let mut thirty_one_count = 0;
         loop {
@@ -121,31 +136,49 @@ impl Rules {
 
 impl ChallengeConfig for Day19 {
     fn title(&self) -> &str {
-        return "Day 19: Monster Messages";
+        return Self::TITLE;
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["parsing", "grammar", "regex"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
         let groups = input.split_sections();
         if groups.len() != 2 {
             return Err(ChallengeError::new("Expected 2 groups").into());
         }
 
-        let mut rules = Rules::new(groups[0].as_str())?;
+        let mut rules = Rules::new(groups[0].as_str(), context)?;
         let messages: Vec<&str> = groups[1]
             .split_whitespace()
             .map(|x| x.trim())
             .filter(|x| !x.is_empty())
             .collect();
-        let valid = rules.resolve_id(0)?;
+        if part == 1 {
+            let valid = rules.resolve_id(0)?;
+            let part_one: usize = messages.iter().filter(|m| valid.contains(**m)).count();
+            return Ok(Solution::for_part(part, part_one));
+        } else if part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
-        let part_one: usize = messages.iter().filter(|m| valid.contains(**m)).count();
-        let part_two_matches: Vec<&&str> = messages
-            .iter()
-            .filter(|m| rules.matches_forty_two_and_thirty_one(m).unwrap_or(false))
-            .collect();
-        let part_two: usize = part_two_matches.len();
+        let mut part_two: usize = 0;
+        for message in messages {
+            context.check()?;
+            if rules
+                .matches_forty_two_and_thirty_one(message)
+                .unwrap_or(false)
+            {
+                part_two += 1;
+            }
+        }
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, part_two));
     }
 }
 
@@ -211,6 +244,11 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day = Day19 {};
-        assert_eq!(day.solve(input).unwrap(), expected);
+        assert_eq!(
+            day.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
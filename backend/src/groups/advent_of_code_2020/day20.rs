@@ -5,9 +5,21 @@ use ndarray::{Array, Array2, Axis};
 use num_integer::Roots;
 use rand::seq::SliceRandom;
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day20 {}
+
+register_challenge!(AdventOfCode2020, Day20);
+
+impl Day20 {
+    pub const TITLE: &'static str = "Day 20: Jurassic Jigsaw";
+    pub const ID: &'static str = "day-20-jurassic-jigsaw";
+}
 trait FlipRotate {
     fn rotate(&mut self);
     fn flip_horizontal(&mut self);
@@ -54,10 +66,7 @@ impl Tile {
                 array_lines[i]
                     .trim()
                     .chars()
-                    .map(|x| match x {
-                        '#' => true,
-                        _ => false,
-                    })
+                    .map(|x| x == '#')
                     .collect::<Vec<bool>>(),
             ));
         }
@@ -77,7 +86,7 @@ impl Tile {
             self.array.column(0),
         ]
         .iter()
-        .map(|x| x.iter().map(|e| *e).collect::<Vec<bool>>())
+        .map(|x| x.iter().copied().collect::<Vec<bool>>())
         .collect();
     }
 
@@ -114,10 +123,18 @@ impl Tile {
 
 impl ChallengeConfig for Day20 {
     fn title(&self) -> &str {
-        return "Day 20: Jurassic Jigsaw";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["grid", "image", "search"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let mut tiles: Vec<Tile> = input
             .split("Tile")
             .map(|x| x.trim())
@@ -134,7 +151,7 @@ impl ChallengeConfig for Day20 {
                         continue;
                     }
                     let other_sides = other_tile.get_sides();
-                    if other_sides.contains(&side.iter().rev().map(|x| *x).collect())
+                    if other_sides.contains(&side.iter().rev().copied().collect())
                         || other_sides.contains(side)
                     {
                         tile.adjacent[i] = Some(other_tile.id);
@@ -148,7 +165,12 @@ impl ChallengeConfig for Day20 {
             .filter(|t| t.adjacent.iter().filter(|x| x.is_none()).count() == 2)
             .collect();
 
-        let part_one: usize = corner_tiles.iter().map(|x| x.id).product::<usize>();
+        if part == 1 {
+            let part_one: usize = corner_tiles.iter().map(|x| x.id).product::<usize>();
+            return Ok(Solution::for_part(part, part_one));
+        } else if part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
         // Includes corners
         let side_tiles: Vec<&Tile> = tiles
@@ -164,16 +186,16 @@ impl ChallengeConfig for Day20 {
             for y in 0..size {
                 let new_value: usize;
                 if x == 0 && y == 0 {
-                    new_value = corner_tiles.iter().next().unwrap().id;
+                    new_value = corner_tiles.first().unwrap().id;
                 } else if x == 0 {
-                    let previous = array_of_tiles.get((0, y - 1)).unwrap().clone();
+                    let previous = *array_of_tiles.get((0, y - 1)).unwrap();
                     new_value = side_tiles
                         .iter()
                         .find(|t| !handled.contains(&t.id) && t.adjacent.contains(&Some(previous)))
                         .unwrap()
                         .id;
                 } else if y == 0 {
-                    let previous_x = array_of_tiles.get((x - 1, y)).unwrap().clone();
+                    let previous_x = *array_of_tiles.get((x - 1, y)).unwrap();
                     new_value = side_tiles
                         .iter()
                         .find(|t| {
@@ -182,8 +204,8 @@ impl ChallengeConfig for Day20 {
                         .unwrap()
                         .id;
                 } else {
-                    let previous_x = array_of_tiles.get((x - 1, y)).unwrap().clone();
-                    let previous_y = array_of_tiles.get((x, y - 1)).unwrap().clone();
+                    let previous_x = *array_of_tiles.get((x - 1, y)).unwrap();
+                    let previous_y = *array_of_tiles.get((x, y - 1)).unwrap();
                     new_value = tiles
                         .iter()
                         .find(|t| {
@@ -219,7 +241,7 @@ impl ChallengeConfig for Day20 {
             let tile = tiles.iter_mut().find(|t| t.id == *tile_id).unwrap();
             while (0..3).any(|x| tile.adjacent[x].as_ref() != expected[x]) {
                 // Random flip and rotate till it works :shrug:
-                match (vec![0, 1, 2]).choose(&mut rand::thread_rng()).unwrap() {
+                match [0, 1, 2].choose(&mut rand::thread_rng()).unwrap() {
                     0 => tile.flip_horizontal(),
                     1 => tile.flip_vertical(),
                     2 => tile.rotate(),
@@ -265,16 +287,16 @@ impl ChallengeConfig for Day20 {
 
         while sea_monsters == 0 {
             for ((x, y), item) in final_array.indexed_iter() {
-                if x == 0 || *item == false || y + 20 > final_array.ncols() {
+                if x == 0 || !*item || y + 20 > final_array.ncols() {
                     continue;
                 }
 
                 let mut good = true;
                 for ((m_x, m_y), m_val) in monster_shape.indexed_iter() {
-                    if *m_val == false {
+                    if !*m_val {
                         continue;
                     }
-                    if final_array[(x + m_x - 1, y + m_y)] != true {
+                    if !final_array[(x + m_x - 1, y + m_y)] {
                         good = false;
                         break;
                     }
@@ -285,7 +307,7 @@ impl ChallengeConfig for Day20 {
                 }
             }
             if sea_monsters == 0 {
-                match (vec![0, 1, 2]).choose(&mut rand::thread_rng()).unwrap() {
+                match [0, 1, 2].choose(&mut rand::thread_rng()).unwrap() {
                     0 => final_array.rotate(),
                     1 => final_array.flip_vertical(),
                     2 => final_array.flip_horizontal(),
@@ -296,7 +318,7 @@ impl ChallengeConfig for Day20 {
 
         let part_two: usize = final_array.iter().filter(|x| **x).count() - sea_monsters * 15;
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, part_two));
     }
 }
 
@@ -423,7 +445,12 @@ mod tests {
     )]
     fn solve(tiles: &str, expected: &str) {
         let day = Day20 {};
-        assert_eq!(day.solve(tiles).unwrap(), expected);
+        assert_eq!(
+            day.solve(tiles, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 
     #[rstest(
@@ -3,15 +3,35 @@ use std::collections::{HashMap, HashSet};
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 pub struct Day21 {}
 
+register_challenge!(AdventOfCode2020, Day21);
+
+impl Day21 {
+    pub const TITLE: &'static str = "Day 21: Allergen Assessment";
+    pub const ID: &'static str = "day-21-allergen-assessment";
+}
+
 impl ChallengeConfig for Day21 {
     fn title(&self) -> &str {
-        return "Day 21: Allergen Assessment";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["parsing", "sets", "constraint solving"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let foods: Vec<&str> = input
             .split("\n")
             .map(str::trim)
@@ -29,7 +49,7 @@ impl ChallengeConfig for Day21 {
                 .map(str::trim)
                 .filter(|x| !x.is_empty())
                 .collect();
-            all_ingredients.append(&mut ingredients.clone().iter().map(|x| *x).collect());
+            all_ingredients.append(&mut ingredients.iter().copied().collect());
             let allergens: Vec<&str> = food
                 .strip_suffix(")")
                 .unwrap()
@@ -45,7 +65,7 @@ impl ChallengeConfig for Day21 {
                 if !possible_allergens.contains_key(allergen) {
                     possible_allergens.insert(
                         allergen,
-                        ingredients.iter().map(|x| *x).collect::<HashSet<&str>>(),
+                        ingredients.iter().copied().collect::<HashSet<&str>>(),
                     );
                 } else {
                     let possible_ingredients = possible_allergens.get(allergen).unwrap().clone();
@@ -53,7 +73,7 @@ impl ChallengeConfig for Day21 {
                         allergen,
                         possible_ingredients
                             .intersection(&ingredients)
-                            .map(|x| *x)
+                            .copied()
                             .collect(),
                     );
                 }
@@ -75,13 +95,17 @@ impl ChallengeConfig for Day21 {
             }
         }
 
-        let found_allergens: HashSet<&str> = possible_allergens
-            .iter()
-            .filter(|(_, v)| v.len() == 1)
-            .map(|(_, v)| *v.iter().next().unwrap())
-            .collect();
-        all_ingredients.retain(|x| !found_allergens.contains(x));
-        let part_one: usize = all_ingredients.len();
+        if part == 1 {
+            let found_allergens: HashSet<&str> = possible_allergens
+                .iter()
+                .filter(|(_, v)| v.len() == 1)
+                .map(|(_, v)| *v.iter().next().unwrap())
+                .collect();
+            all_ingredients.retain(|x| !found_allergens.contains(x));
+            return Ok(Solution::for_part(part, all_ingredients.len()));
+        } else if part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
         let part_two: String = possible_allergens
             .iter()
@@ -89,7 +113,7 @@ impl ChallengeConfig for Day21 {
             .map(|(_, v)| *v.iter().next().unwrap())
             .join(",");
 
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, part_two));
     }
 }
 
@@ -112,6 +136,11 @@ mod tests {
     )]
     fn solve(foods: &str, expected: &str) {
         let day = Day21 {};
-        assert_eq!(day.solve(foods).unwrap(), expected);
+        assert_eq!(
+            day.solve(foods, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -1,13 +1,23 @@
 use anyhow::Result;
 
+use super::config::AdventOfCode2020;
 use crate::{
-    groups::challenge_config::{ChallengeConfig, ChallengeError},
+    groups::{
+        challenge_config::{ChallengeConfig, ChallengeError},
+        solution::Solution,
+        solve_context::SolveContext,
+    },
     utils::InputUtils,
 };
 
 pub struct Day22 {}
 
+register_challenge!(AdventOfCode2020, Day22);
+
 impl Day22 {
+    pub const TITLE: &'static str = "Day 22: Crab Combat";
+    pub const ID: &'static str = "day-22-crab-combat";
+
     fn parse_deck(input: &str) -> Result<Vec<usize>> {
         return Ok(input
             .split_whitespace()
@@ -25,10 +35,15 @@ impl Day22 {
         return result;
     }
 
-    fn solve_regular(deck1: Vec<usize>, deck2: Vec<usize>) -> usize {
+    fn solve_regular(
+        deck1: Vec<usize>,
+        deck2: Vec<usize>,
+        context: &SolveContext,
+    ) -> Result<usize> {
         let mut deck1 = deck1.clone();
         let mut deck2 = deck2.clone();
-        while deck1.len() > 0 && deck2.len() > 0 {
+        while !deck1.is_empty() && !deck2.is_empty() {
+            context.check()?;
             let first = deck1.remove(0);
             let second = deck2.remove(0);
             if first > second {
@@ -39,63 +54,82 @@ impl Day22 {
         }
 
         let mut winning_deck = deck1;
-        if deck2.len() > 0 {
+        if !deck2.is_empty() {
             winning_deck = deck2;
         }
-        return Self::count_winner(winning_deck);
+        return Ok(Self::count_winner(winning_deck));
     }
 
-    fn solve_recursive(deck1: Vec<usize>, deck2: Vec<usize>) -> usize {
-        return Self::count_winner(Self::play_recursive(deck1, deck2).1);
+    fn solve_recursive(
+        deck1: Vec<usize>,
+        deck2: Vec<usize>,
+        context: &SolveContext,
+    ) -> Result<usize> {
+        return Ok(Self::count_winner(
+            Self::play_recursive(deck1, deck2, context)?.1,
+        ));
     }
 
-    fn play_recursive(deck1: Vec<usize>, deck2: Vec<usize>) -> (bool, Vec<usize>) {
+    fn play_recursive(
+        deck1: Vec<usize>,
+        deck2: Vec<usize>,
+        context: &SolveContext,
+    ) -> Result<(bool, Vec<usize>)> {
         let mut deck1 = deck1.clone();
         let mut deck2 = deck2.clone();
         let mut cont = true;
         let mut previous_rounds: Vec<[Vec<usize>; 2]> = vec![];
         while cont {
+            context.check()?;
             if previous_rounds
                 .iter()
                 .any(|previous| deck1 == previous[0] && deck2 == previous[1])
             {
-                return (true, deck1);
+                return Ok((true, deck1));
             }
             previous_rounds.push([deck1.clone(), deck2.clone()]);
 
             let first = deck1.remove(0);
             let second = deck2.remove(0);
-            let player_one_wins: bool;
-            if deck1.len() >= first && deck2.len() >= second {
-                player_one_wins = Self::play_recursive(
+            let player_one_wins = if deck1.len() >= first && deck2.len() >= second {
+                Self::play_recursive(
                     deck1.clone()[..first].to_vec(),
                     deck2.clone()[..second].to_vec(),
-                )
-                .0;
+                    context,
+                )?
+                .0
             } else {
-                player_one_wins = first > second;
-            }
+                first > second
+            };
             if player_one_wins {
                 deck1.append(&mut vec![first, second]);
             } else {
                 deck2.append(&mut vec![second, first]);
             }
-            cont = deck1.len() > 0 && deck2.len() > 0
+            cont = !deck1.is_empty() && !deck2.is_empty()
         }
-        if deck1.len() > 0 {
-            return (true, deck1);
+        if !deck1.is_empty() {
+            return Ok((true, deck1));
         } else {
-            return (false, deck2);
+            return Ok((false, deck2));
         }
     }
 }
 
 impl ChallengeConfig for Day22 {
     fn title(&self) -> &str {
-        return "Day 22: Crab Combat";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["game", "simulation", "recursion"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
         let groups = input
             .replace("Player 1:\n", "")
             .replace("Player 2:\n", "")
@@ -107,10 +141,17 @@ impl ChallengeConfig for Day22 {
         let deck1 = Self::parse_deck(groups[0].as_str())?;
         let deck2 = Self::parse_deck(groups[1].as_str())?;
 
-        let part_one = Self::solve_regular(deck1.clone(), deck2.clone());
-        let part_two = Self::solve_recursive(deck1.clone(), deck2.clone());
-
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return match part {
+            1 => Ok(Solution::for_part(
+                part,
+                Self::solve_regular(deck1, deck2, context)?,
+            )),
+            2 => Ok(Solution::for_part(
+                part,
+                Self::solve_recursive(deck1, deck2, context)?,
+            )),
+            _ => Err(ChallengeError::invalid_part(part).into()),
+        };
     }
 }
 
@@ -142,6 +183,11 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day = Day22 {};
-        assert_eq!(day.solve(input).unwrap(), expected);
+        assert_eq!(
+            day.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
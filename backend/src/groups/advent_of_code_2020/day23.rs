@@ -1,18 +1,37 @@
 use anyhow::Result;
 
-use crate::groups::challenge_config::ChallengeConfig;
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
+/// Number of moves between two cancellation checks
+const CHECK_INTERVAL: usize = 1 << 20;
+
 pub struct Day23 {}
 
+register_challenge!(AdventOfCode2020, Day23);
+
 impl Day23 {
-    fn play(cups: Vec<usize>, iterations: usize, cup_count: Option<usize>) -> Vec<usize> {
+    pub const TITLE: &'static str = "Day 23: Crab Cups";
+    pub const ID: &'static str = "day-23-crab-cups";
+
+    fn play(
+        cups: Vec<usize>,
+        iterations: usize,
+        cup_count: Option<usize>,
+        context: &SolveContext,
+    ) -> Result<Vec<usize>> {
         let mut cups = cups.clone();
-        let max = if cup_count.is_none() {
-            9
-        } else {
-            for i in 10..=cup_count.unwrap() {
-                cups.push(i);
+        let max = match cup_count {
+            None => 9,
+            Some(cup_count) => {
+                for i in 10..=cup_count {
+                    cups.push(i);
+                }
+                cup_count
             }
-            cup_count.unwrap()
         };
 
         // An array that points value -> next value
@@ -20,7 +39,11 @@ impl Day23 {
         (0..max).for_each(|i| link_list[cups[i]] = cups[(i + 1) % max]);
 
         let mut cur_cup = cups[0];
-        for _ in 0..iterations {
+        for iteration in 0..iterations {
+            if iteration % CHECK_INTERVAL == 0 {
+                context.check()?;
+                context.progress().set_steps(iteration, iterations);
+            }
             // Remove elements from link list
             let p1 = link_list[cur_cup];
             let p2 = link_list[p1];
@@ -50,32 +73,46 @@ impl Day23 {
             i = link_list[i];
             result.push(i);
         }
-        return result;
+        return Ok(result);
     }
 }
 
 impl ChallengeConfig for Day23 {
     fn title(&self) -> &str {
-        return "Day 23: Crab Cups";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["game", "simulation", "linked list"];
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
         let cups: Vec<usize> = input
             .chars()
             .map(|c| c.to_string().parse::<usize>())
             .collect::<Result<_, _>>()?;
 
-        let first_result = Self::play(cups.clone(), 100, None);
-        let part_one = first_result[1..]
-            .iter()
-            .map(usize::to_string)
-            .collect::<Vec<_>>()
-            .join("");
-
-        let second_result = Self::play(cups.clone(), 10000000, Some(1000000));
-        let part_two = second_result[1] * second_result[2];
-
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        match part {
+            1 => {
+                let first_result = Self::play(cups, 100, None, context)?;
+                let part_one = first_result[1..]
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join("");
+                return Ok(Solution::for_part(part, part_one));
+            }
+            2 => {
+                let second_result = Self::play(cups, 10000000, Some(1000000), context)?;
+                let part_two = second_result[1] * second_result[2];
+                return Ok(Solution::for_part(part, part_two));
+            }
+            _ => return Err(ChallengeError::invalid_part(part).into()),
+        }
     }
 }
 
@@ -92,6 +129,11 @@ mod tests {
     )]
     fn solve(cups: &str, expected: &str) {
         let day = Day23 {};
-        assert_eq!(day.solve(cups).unwrap(), expected);
+        assert_eq!(
+            day.solve(cups, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
@@ -2,7 +2,12 @@ use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 #[derive(Copy, Clone)]
 enum Move {
@@ -97,12 +102,27 @@ impl Move {
 
 pub struct Day24 {}
 
+register_challenge!(AdventOfCode2020, Day24);
+
+impl Day24 {
+    pub const TITLE: &'static str = "Day 24: Lobby Layout";
+    pub const ID: &'static str = "day-24-lobby-layout";
+}
+
 impl ChallengeConfig for Day24 {
     fn title(&self) -> &str {
-        return "Day 24: Lobby Layout";
+        return Self::TITLE;
+    }
+
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn tags(&self) -> Vec<&str> {
+        return vec!["hex grid", "cellular automaton", "simulation"];
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
         let tiles: Vec<Vec<Move>> = input
             .split_whitespace()
             .map(|s| {
@@ -137,7 +157,11 @@ impl ChallengeConfig for Day24 {
             }
         }
 
-        let part_one = black_tiles.len();
+        if part == 1 {
+            return Ok(Solution::for_part(part, black_tiles.len()));
+        } else if part != 2 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
 
         for _ in 1..=100 {
             let mut adjacent_black_count: HashMap<(i32, i32), u32> = HashMap::new();
@@ -157,9 +181,9 @@ impl ChallengeConfig for Day24 {
                 .collect();
 
             for tile in black_tiles.clone().iter() {
-                let adjacent_count = *adjacent_black_count.get(&tile).unwrap_or(&0);
+                let adjacent_count = *adjacent_black_count.get(tile).unwrap_or(&0);
                 if adjacent_count == 0 || adjacent_count > 2 {
-                    black_tiles.remove(&tile);
+                    black_tiles.remove(tile);
                 }
             }
             for tile in to_add {
@@ -167,9 +191,7 @@ impl ChallengeConfig for Day24 {
             }
         }
 
-        let part_two = black_tiles.len();
-
-        return Ok(format!("Part 1: {}\nPart 2: {}", part_one, part_two).to_string());
+        return Ok(Solution::for_part(part, black_tiles.len()));
     }
 }
 
@@ -208,6 +230,11 @@ mod tests {
     )]
     fn solve(tiles: &str, expected: &str) {
         let day = Day24 {};
-        assert_eq!(day.solve(tiles).unwrap(), expected);
+        assert_eq!(
+            day.solve(tiles, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
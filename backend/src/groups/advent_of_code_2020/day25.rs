@@ -1,10 +1,20 @@
 use anyhow::Result;
 
-use crate::groups::challenge_config::{ChallengeConfig, ChallengeError};
+use super::config::AdventOfCode2020;
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
 
 pub struct Day25 {}
 
+register_challenge!(AdventOfCode2020, Day25);
+
 impl Day25 {
+    pub const TITLE: &'static str = "Day 25: Combo Breaker";
+    pub const ID: &'static str = "day-25-combo-breaker";
+
     fn find_loop_size(key: u64) -> Result<u64> {
         let mut value = 1;
         for i in 1..100000000 {
@@ -26,10 +36,25 @@ impl Day25 {
 
 impl ChallengeConfig for Day25 {
     fn title(&self) -> &str {
-        return "Day 25: Combo Breaker";
+        return Self::TITLE;
     }
 
-    fn solve(&self, input: &str) -> Result<String> {
+    fn id(&self) -> String {
+        return Self::ID.to_owned();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return vec!["cryptography", "number theory", "modular arithmetic"];
+    }
+
+    fn part_count(&self) -> usize {
+        return 1;
+    }
+
+    fn solve_part(&self, input: &str, part: usize, _context: &SolveContext) -> Result<Solution> {
+        if part != 1 {
+            return Err(ChallengeError::invalid_part(part).into());
+        }
         let lines: Vec<&str> = input
             .split("\n")
             .map(|x| x.trim())
@@ -47,7 +72,7 @@ impl ChallengeConfig for Day25 {
             result = (result * door_public_key) % 20201227;
         }
 
-        return Ok(format!("Result: {}", result).to_string());
+        return Ok(Solution::new().with_part("Result", result));
     }
 }
 
@@ -73,6 +98,11 @@ mod tests {
     )]
     fn solve(input: &str, expected: &str) {
         let day = Day25 {};
-        assert_eq!(day.solve(input).unwrap(), expected);
+        assert_eq!(
+            day.solve(input, &SolveContext::default())
+                .unwrap()
+                .to_string(),
+            expected
+        );
     }
 }
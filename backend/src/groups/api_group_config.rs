@@ -1,13 +1,29 @@
-use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
 
-use super::{challenge_config::ChallengeConfig, group_config::GroupConfig};
+use reqwest::{header::CONTENT_TYPE, Client, RequestBuilder, Response};
 
-#[derive(Serialize, Deserialize)]
+use super::{
+    cancellation::CancellationToken,
+    challenge_config::{ChallengeConfig, ChallengeError},
+    group_config::{check_challenge_ids, GroupConfig, GroupUnavailableError},
+    health::{GroupHealth, HealthStatus},
+    remote_client::{self, RemoteClient},
+    remote_protocol::{ListedChallenge, SolveRequest, SolveResponse, PROTOCOL_VERSION},
+    solution::Solution,
+    solve_context::SolveContext,
+};
+use crate::config::{RemoteGroupConfig, RemoteProtocol};
+
+#[derive(Clone)]
 pub struct ApiChallenge {
     id: String,
     title: String,
     description: String,
-    port: Option<i32>,
+    tags: Vec<String>,
+    client: Arc<RemoteClient>,
 }
 
 impl ChallengeConfig for ApiChallenge {
@@ -15,79 +31,476 @@ impl ChallengeConfig for ApiChallenge {
         return self.title.as_str();
     }
 
+    fn id(&self) -> String {
+        return self.id.clone();
+    }
+
     fn description(&self) -> &str {
         return self.description.as_str();
     }
 
-    fn solve(&self, input: &str) -> anyhow::Result<String> {
-        let client = reqwest::blocking::Client::new();
-        let res = client
-            .post(format!(
-                "http://localhost:{}/solve/{}",
-                self.port.unwrap(),
-                self.id
-            ))
-            .body(String::from(input))
-            .send()?;
-        return Ok(res.text()?);
+    fn tags(&self) -> Vec<&str> {
+        return self.tags.iter().map(String::as_str).collect();
+    }
+
+    fn solve_part(
+        &self,
+        input: &str,
+        part: usize,
+        context: &SolveContext,
+    ) -> anyhow::Result<Solution> {
+        // Remote services always solve all parts, only keep the requested one
+        let solution = self.solve(input, context)?;
+        return match solution.nth_part(part) {
+            Some(s) => Ok(s),
+            None => Err(ChallengeError::invalid_part(part).into()),
+        };
+    }
+
+    fn solves_parts_together(&self) -> bool {
+        return true;
+    }
+
+    fn solve(&self, input: &str, context: &SolveContext) -> anyhow::Result<Solution> {
+        let token = context.token();
+        token.check()?;
+        return match self.client.remote().protocol {
+            RemoteProtocol::Text => remote_client::block_on(self.solve_text(input, token)),
+            RemoteProtocol::V1 => remote_client::block_on(self.solve_v1(input, token)),
+        };
+    }
+}
+
+impl ApiChallenge {
+    async fn solve_text(&self, input: &str, token: &CancellationToken) -> anyhow::Result<Solution> {
+        let url = self.client.remote().endpoint(&format!("solve/{}", self.id));
+        let res = self
+            .send(|c| c.post(&url).body(String::from(input)), token)
+            .await?;
+        let status = res.status();
+        let text = res.text().await?;
+        // The text services answer errors with a message and an error status
+        if !status.is_success() {
+            let message = match text.trim() {
+                "" => status.to_string(),
+                t => t.to_owned(),
+            };
+            return Err(ChallengeError::new(&message).into());
+        }
+        return Ok(Solution::from_text(&text));
+    }
+
+    async fn solve_v1(&self, input: &str, token: &CancellationToken) -> anyhow::Result<Solution> {
+        let body = SolveRequest::new(input, token.remaining().map(|r| r.as_millis() as u64));
+        let url = self
+            .client
+            .remote()
+            .endpoint(&format!("v{}/solve/{}", PROTOCOL_VERSION, self.id));
+        let res = self.send(|c| c.post(&url).json(&body), token).await?;
+        let status = res.status();
+        let is_json = res
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|c| c.to_str().ok())
+            .is_some_and(|c| c.starts_with("application/json"));
+        // Errors raised before reaching the service, e.g. by a proxy, aren't in the protocol
+        if !is_json {
+            let text = res.text().await?;
+            let message = format!("Unexpected {} response: {}", status, text.trim());
+            return Err(ChallengeError::new(&message).into());
+        }
+        let response: SolveResponse = res.json().await?;
+        return Ok(response.into_solution()?);
+    }
+
+    /// Sends the solve once, the remote solve can't be cancelled but it shouldn't outlive the
+    /// deadline
+    async fn send<F: FnOnce(&Client) -> RequestBuilder>(
+        &self,
+        request: F,
+        token: &CancellationToken,
+    ) -> anyhow::Result<Response> {
+        let timeout = Duration::from_secs(self.client.remote().timeout_secs);
+        let timeout = token.remaining().map_or(timeout, |r| r.min(timeout));
+        return match self.client.send(|c| request(c).timeout(timeout)).await {
+            Ok(r) => Ok(r),
+            Err(e)
+                if e.downcast_ref::<reqwest::Error>()
+                    .is_some_and(|e| e.is_timeout()) =>
+            {
+                Err(ChallengeError::timed_out().into())
+            }
+            Err(e) => Err(e),
+        };
+    }
+}
+
+async fn list_challenges(client: &Arc<RemoteClient>) -> anyhow::Result<Vec<ApiChallenge>> {
+    let response = client.get("list/").await?.error_for_status()?;
+    let listed: Vec<ListedChallenge> = response.json().await?;
+    let challenges: Vec<ApiChallenge> = listed
+        .into_iter()
+        .map(|c| ApiChallenge {
+            id: c.id,
+            title: c.title,
+            description: c.description,
+            tags: c.tags,
+            client: client.clone(),
+        })
+        .collect();
+    check_challenge_ids(&challenges)?;
+    return Ok(challenges);
+}
+
+/// Last challenge listing of a remote group, shared with its background refresh
+#[derive(Default)]
+struct Listing {
+    challenges: Option<Vec<ApiChallenge>>,
+    checked_at: Option<Instant>,
+    refreshing: bool,
+    last_error: Option<String>,
+}
+
+impl Listing {
+    fn update(&mut self, result: anyhow::Result<Vec<ApiChallenge>>) {
+        match result {
+            Ok(challenges) => {
+                self.challenges = Some(challenges);
+                self.last_error = None;
+            }
+            Err(e) => self.last_error = Some(e.to_string()),
+        }
+        self.checked_at = Some(Instant::now());
+        self.refreshing = false;
+    }
+
+    fn health(&self) -> GroupHealth {
+        let status = match (&self.challenges, &self.last_error) {
+            (None, _) => HealthStatus::Down,
+            (Some(_), Some(_)) => HealthStatus::Degraded,
+            (Some(_), None) => HealthStatus::Up,
+        };
+        return GroupHealth {
+            status: status,
+            last_error: self.last_error.clone(),
+        };
     }
 }
 
+/// Group listing its challenges from a remote service. The listing is cached for the TTL of
+/// the group, then refreshed in the background while the expired one is still served
 pub struct ApiGroupConfig {
-    name: String,
-    challenges_url: String,
-    port: i32,
+    client: Arc<RemoteClient>,
+    listing: Arc<Mutex<Listing>>,
+    // Held during the first listing so that concurrent callers wait for it instead of each
+    // fetching it, while the listing itself stays free for refreshes
+    first_listing: Mutex<()>,
 }
 
 impl ApiGroupConfig {
-    pub fn new(name: &str, challenges_url: &str, default_port: i32, port_envvar: &str) -> Self
+    pub fn new(remote: RemoteGroupConfig) -> Self
     where
         Self: Sized,
     {
-        let port = match std::env::var(port_envvar) {
-            Ok(p) => p.parse::<i32>().unwrap_or(default_port),
-            Err(_) => default_port,
-        };
         return ApiGroupConfig {
-            name: String::from(name),
-            challenges_url: String::from(challenges_url),
-            port: port,
+            client: Arc::new(RemoteClient::new(remote)),
+            listing: Arc::new(Mutex::new(Listing::default())),
+            first_listing: Mutex::new(()),
         };
     }
 
-    fn list_challenges(port: i32) -> anyhow::Result<Vec<ApiChallenge>> {
-        let client = reqwest::blocking::Client::new();
-        let res = client
-            .get(format!("http://localhost:{}/list/", port))
-            .send()?;
-        let mut v: Vec<ApiChallenge> = res.json()?;
-        v.iter_mut().for_each(|i| i.port = Some(port));
-        return Ok(v);
+    /// Locks the listing once it was fetched at least once, refreshing it if expired
+    fn listing(&self) -> MutexGuard<'_, Listing> {
+        // Nothing can be served before the first listing, so it is fetched in place, without
+        // holding the listing during the requests and their retries
+        if self.listing.lock().unwrap().checked_at.is_none() {
+            let _first_listing = self.first_listing.lock().unwrap();
+            if self.listing.lock().unwrap().checked_at.is_none() {
+                let result = remote_client::block_on(list_challenges(&self.client));
+                self.listing.lock().unwrap().update(result);
+            }
+        }
+        let mut listing = self.listing.lock().unwrap();
+        let ttl = Duration::from_secs(self.client.remote().cache_ttl_secs);
+        match listing.checked_at {
+            Some(checked_at) if checked_at.elapsed() >= ttl && !listing.refreshing => {
+                listing.refreshing = true;
+                let client = self.client.clone();
+                let shared = self.listing.clone();
+                remote_client::spawn(async move {
+                    let result = list_challenges(&client).await;
+                    shared.lock().unwrap().update(result);
+                });
+            }
+            _ => {}
+        }
+        return listing;
     }
 }
 
 impl GroupConfig for ApiGroupConfig {
     fn name(&self) -> &str {
-        return self.name.as_str();
+        return self.client.remote().name.as_str();
+    }
+    fn id(&self) -> String {
+        return self.client.remote().id();
     }
     fn url(&self) -> &str {
-        return self.challenges_url.as_str();
-    }
-
-    fn challenges(&self) -> Vec<Box<dyn ChallengeConfig>> {
-        let challenges = match ApiGroupConfig::list_challenges(self.port) {
-            Ok(v) => v
-                .into_iter()
-                .map(|e| Box::new(e) as Box<dyn ChallengeConfig>)
-                .collect::<Vec<_>>(),
-            Err(err) => {
-                eprintln!(
-                    "Got an error while listing challenges for Advent of Code 2019: {}",
-                    err
-                );
-                vec![]
-            }
+        return self.client.remote().url.as_str();
+    }
+
+    /// Serves the last successful listing, even if the latest one failed
+    fn challenges(&self) -> Result<Vec<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        let listing = self.listing();
+        return match &listing.challenges {
+            Some(challenges) => Ok(challenges
+                .iter()
+                .map(|e| Box::new(e.clone()) as Box<dyn ChallengeConfig>)
+                .collect()),
+            None => Err(GroupUnavailableError::new(
+                self.name(),
+                listing.last_error.as_deref().unwrap_or_default(),
+            )),
+        };
+    }
+
+    fn health(&self) -> GroupHealth {
+        return self.listing().health();
+    }
+
+    /// Lists the challenges right away, even before the TTL expired. The previous listing is
+    /// still served if it fails
+    fn refresh(&self) {
+        let result = remote_client::block_on(list_challenges(&self.client));
+        self.listing.lock().unwrap().update(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::{
+        challenge_config::ChallengeErrorKind,
+        stub_server::{hung_server, stub_server, StubRequest, StubResponse, LIST},
+    };
+
+    fn remote(base_url: String) -> RemoteGroupConfig {
+        return RemoteGroupConfig {
+            name: "Stub".to_owned(),
+            base_url: base_url,
+            timeout_secs: 5,
+            cache_ttl_secs: 60,
+            bearer_token: Some("abc".to_owned()),
+            auth_header: Some("X-Api-Key: def".to_owned()),
+            ..RemoteGroupConfig::default()
+        };
+    }
+
+    /// Solves the only challenge of the stub with the given protocol
+    fn solve(
+        protocol: RemoteProtocol,
+        response: StubResponse,
+    ) -> (anyhow::Result<Solution>, StubRequest) {
+        let (base_url, requests) = stub_server(vec![LIST, response]);
+        let group = ApiGroupConfig::new(RemoteGroupConfig {
+            protocol: protocol,
+            ..remote(base_url)
+        });
+        let result = group.challenges().unwrap()[0].solve("input", &SolveContext::default());
+        requests.recv().unwrap();
+        return (result, requests.recv().unwrap());
+    }
+
+    #[rstest(prefix, case(""), case("/aoc"), case("/aoc/"))]
+    fn list_and_solve(prefix: &str) {
+        let (base_url, requests) = stub_server(vec![
+            LIST,
+            (200, "text/plain", "Part 1: 42\nPart 2: abc\n##"),
+        ]);
+        let group = ApiGroupConfig::new(RemoteGroupConfig {
+            protocol: RemoteProtocol::Text,
+            ..remote(format!("{}{}", base_url, prefix))
+        });
+        let challenges = group.challenges().unwrap();
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0].title(), "Day 1");
+        let solution = challenges[0]
+            .solve_part("input", 2, &SolveContext::default())
+            .unwrap();
+        assert_eq!(
+            solution,
+            Solution::new().with_part_notes("Part 2", "abc", "##")
+        );
+
+        let prefix = prefix.trim_end_matches('/');
+        let list = requests.recv().unwrap();
+        assert_eq!(list.head[0], format!("GET {}/list/ HTTP/1.1", prefix));
+        let solve = requests.recv().unwrap();
+        assert_eq!(solve.head[0], format!("POST {}/solve/1 HTTP/1.1", prefix));
+        assert_eq!(solve.body, "input");
+        for request in [list, solve].iter() {
+            assert!(request.has_header("authorization: Bearer abc"));
+            assert!(request.has_header("x-api-key: def"));
+        }
+    }
+
+    #[rstest(
+        response,
+        expected,
+        case(
+            (200, "text/plain", "Part 1: 42"),
+            Ok(Solution::new().with_part("Part 1", 42))
+        ),
+        case(
+            (400, "text/plain", "Got an error while solving: invalid input\n"),
+            Err((ChallengeErrorKind::Failed, "Got an error while solving: invalid input"))
+        ),
+        case(
+            (500, "text/plain", ""),
+            Err((ChallengeErrorKind::Failed, "500 Internal Server Error"))
+        )
+    )]
+    fn solve_text(response: StubResponse, expected: Result<Solution, (ChallengeErrorKind, &str)>) {
+        let (result, request) = solve(RemoteProtocol::Text, response);
+        assert_eq!(request.head[0], "POST /solve/1 HTTP/1.1");
+        let result = result.map_err(|e| {
+            let e = e.downcast::<ChallengeError>().unwrap();
+            (e.kind(), e.to_string())
+        });
+        assert_eq!(result, expected.map_err(|(k, m)| (k, m.to_owned())));
+    }
+
+    #[rstest(
+        response,
+        expected,
+        case(
+            (200, "application/json", r#"{"version": 1, "status": "ok", "parts": [{"name": "Part 1", "value": 42}]}"#),
+            Ok(Solution::new().with_part("Part 1", 42))
+        ),
+        case(
+            (422, "application/json", r#"{"version": 1, "status": "error", "error": "Invalid input"}"#),
+            Err((ChallengeErrorKind::Failed, "Invalid input"))
+        ),
+        case(
+            (200, "application/json", r#"{"version": 1, "status": "timed_out", "duration_ms": 10.5}"#),
+            Err((ChallengeErrorKind::TimedOut, "The solve timed out"))
+        ),
+        case(
+            (404, "text/plain", "404 page not found"),
+            Err((ChallengeErrorKind::Failed, "Unexpected 404 Not Found response: 404 page not found"))
+        )
+    )]
+    fn solve_v1(response: StubResponse, expected: Result<Solution, (ChallengeErrorKind, &str)>) {
+        let (result, request) = solve(RemoteProtocol::V1, response);
+        assert_eq!(request.head[0], "POST /v1/solve/1 HTTP/1.1");
+        assert!(request.has_header("content-type: application/json"));
+        let body: SolveRequest = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body, SolveRequest::new("input", None));
+        let result = result.map_err(|e| {
+            let e = e.downcast::<ChallengeError>().unwrap();
+            (e.kind(), e.to_string())
+        });
+        assert_eq!(result, expected.map_err(|(k, m)| (k, m.to_owned())));
+    }
+
+    #[rstest(protocol, case(RemoteProtocol::Text), case(RemoteProtocol::V1))]
+    fn solve_deadline(protocol: RemoteProtocol) {
+        let challenge = ApiChallenge {
+            id: "1".to_owned(),
+            title: "Day 1".to_owned(),
+            description: String::new(),
+            tags: vec![],
+            client: Arc::new(RemoteClient::new(RemoteGroupConfig {
+                protocol: protocol,
+                ..remote(hung_server())
+            })),
+        };
+        let start = Instant::now();
+        let context =
+            SolveContext::new(CancellationToken::with_timeout(Duration::from_millis(100)));
+        let error = challenge.solve("input", &context).unwrap_err();
+        let error = error.downcast::<ChallengeError>().unwrap();
+        assert_eq!(error.kind(), ChallengeErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn first_listing_keeps_the_listing_free() {
+        let group = Arc::new(ApiGroupConfig::new(RemoteGroupConfig {
+            timeout_secs: 1,
+            list_retries: 0,
+            ..remote(hung_server())
+        }));
+        let listing_group = group.clone();
+        let listing = thread::spawn(move || listing_group.challenges().is_err());
+        thread::sleep(Duration::from_millis(200));
+        assert!(group.listing.try_lock().is_ok());
+        assert!(group.first_listing.try_lock().is_err());
+        assert!(listing.join().unwrap());
+        assert_eq!(group.health().status, HealthStatus::Down);
+    }
+
+    #[test]
+    fn invalid_ca_bundle() {
+        let remote = RemoteGroupConfig {
+            ca_bundle: Some("missing.pem".into()),
+            ..remote("https://localhost".to_owned())
         };
-        return challenges;
+        let group = ApiGroupConfig::new(remote);
+        let error = group.challenges().err().unwrap();
+        assert_eq!(error.group, "Stub");
+        let health = group.health();
+        assert_eq!(health.status, HealthStatus::Down);
+        assert!(health.last_error.is_some());
+    }
+
+    #[rstest(
+        cache_ttl_secs,
+        expected_status,
+        case(60, HealthStatus::Up),
+        case(0, HealthStatus::Degraded)
+    )]
+    fn cached_listing(cache_ttl_secs: u64, expected_status: HealthStatus) {
+        // The stub stops listening after the first listing, so refreshes fail
+        let (base_url, requests) = stub_server(vec![LIST]);
+        let group = ApiGroupConfig::new(RemoteGroupConfig {
+            cache_ttl_secs: cache_ttl_secs,
+            ..remote(base_url)
+        });
+        assert_eq!(group.health(), GroupHealth::up());
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 1"]);
+        assert!(group.challenge("Day 1").unwrap().is_some());
+        let start = Instant::now();
+        while group.listing.lock().unwrap().refreshing && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        // Expired listings are still served while refreshing
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 1"]);
+        assert_eq!(group.health().status, expected_status);
+        assert_eq!(requests.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn refresh() {
+        let listed = (
+            200,
+            "application/json",
+            r#"[{"id": "1", "title": "Day 1"}, {"id": "2", "title": "Day 2"}]"#,
+        );
+        let (base_url, requests) = stub_server(vec![LIST, listed]);
+        let group = ApiGroupConfig::new(remote(base_url));
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 1"]);
+        group.refresh();
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 1", "Day 2"]);
+        assert_eq!(requests.try_iter().count(), 2);
+
+        // The stub stopped listening, the last listing is still served
+        group.refresh();
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 1", "Day 2"]);
+        assert_eq!(group.health().status, HealthStatus::Degraded);
     }
 }
@@ -0,0 +1,97 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+
+use super::challenge_config::ChallengeError;
+
+/// Cooperative cancellation of a solve, shared between the solver and whoever started it.
+/// Clones share the same cancelled flag and deadline
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// Token that is only stopped by calling `cancel`
+    pub fn new() -> Self {
+        return CancellationToken::default();
+    }
+
+    /// Token that also stops once the timeout has elapsed
+    pub fn with_timeout(timeout: Duration) -> Self {
+        return CancellationToken {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            deadline: Some(Instant::now() + timeout),
+        };
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::Relaxed);
+    }
+
+    /// Time left before the deadline, if there is one
+    pub fn remaining(&self) -> Option<Duration> {
+        return self
+            .deadline
+            .map(|d| d.saturating_duration_since(Instant::now()));
+    }
+
+    /// Fails with a cancelled or timed out `ChallengeError` once the solve should stop
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(ChallengeError::cancelled().into());
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(ChallengeError::timed_out().into());
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::challenge_config::ChallengeErrorKind;
+
+    fn error_kind(token: &CancellationToken) -> Option<ChallengeErrorKind> {
+        return token
+            .check()
+            .err()
+            .map(|e| e.downcast::<ChallengeError>().unwrap().kind());
+    }
+
+    #[test]
+    fn cancel() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert_eq!(error_kind(&token), None);
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(error_kind(&token), Some(ChallengeErrorKind::Cancelled));
+    }
+
+    #[test]
+    fn timeout() {
+        assert_eq!(
+            error_kind(&CancellationToken::with_timeout(Duration::from_secs(60))),
+            None
+        );
+        assert_eq!(
+            error_kind(&CancellationToken::with_timeout(Duration::from_secs(0))),
+            Some(ChallengeErrorKind::TimedOut)
+        );
+    }
+}
@@ -3,17 +3,59 @@ use std::fmt;
 use anyhow::Result;
 use thiserror::Error;
 
+use super::{solution::Solution, solve_context::SolveContext};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChallengeErrorKind {
+    Failed,
+    Cancelled,
+    TimedOut,
+    /// The solve used more resources than it is allowed, e.g. the fuel of a WebAssembly module
+    LimitExceeded,
+}
+
 #[derive(Debug, Clone, Error)]
 pub struct ChallengeError {
     message: String,
+    kind: ChallengeErrorKind,
 }
 
 impl ChallengeError {
     pub fn new(message: &str) -> ChallengeError {
         return ChallengeError {
             message: message.to_string(),
+            kind: ChallengeErrorKind::Failed,
+        };
+    }
+
+    pub fn invalid_part(part: usize) -> ChallengeError {
+        return ChallengeError::new(format!("Part {} does not exist", part).as_str());
+    }
+
+    pub fn cancelled() -> ChallengeError {
+        return ChallengeError {
+            message: "The solve was cancelled".to_owned(),
+            kind: ChallengeErrorKind::Cancelled,
         };
     }
+
+    pub fn timed_out() -> ChallengeError {
+        return ChallengeError {
+            message: "The solve timed out".to_owned(),
+            kind: ChallengeErrorKind::TimedOut,
+        };
+    }
+
+    pub fn limit_exceeded(message: &str) -> ChallengeError {
+        return ChallengeError {
+            message: message.to_owned(),
+            kind: ChallengeErrorKind::LimitExceeded,
+        };
+    }
+
+    pub fn kind(&self) -> ChallengeErrorKind {
+        return self.kind;
+    }
 }
 
 impl fmt::Display for ChallengeError {
@@ -22,10 +64,38 @@ impl fmt::Display for ChallengeError {
     }
 }
 
-pub trait ChallengeConfig {
+pub trait ChallengeConfig: Send + Sync {
     fn title(&self) -> &str;
+    /// Stable id of the challenge in its group, a slug used in the API paths and to name the
+    /// saved inputs and answers
+    fn id(&self) -> String;
     fn description(&self) -> &str {
         return "";
     }
-    fn solve(&self, input: &str) -> Result<String>;
+    /// Topics of the challenge, like "graph" or "parsing", matched by the search
+    fn tags(&self) -> Vec<&str> {
+        return vec![];
+    }
+    /// Number of parts that can be solved individually through `solve_part`
+    fn part_count(&self) -> usize {
+        return 2;
+    }
+    /// Solves a single part, starting at 1. Long running solvers should regularly call
+    /// `context.check()` so that the solve stops once it is cancelled or past its deadline,
+    /// and report how far they are through `context.progress()`
+    fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution>;
+    /// Whether `solve_part` solves every part and only keeps the requested one, like the
+    /// challenges of modules answering whole solutions
+    fn solves_parts_together(&self) -> bool {
+        return false;
+    }
+    fn solve(&self, input: &str, context: &SolveContext) -> Result<Solution> {
+        let mut solution = Solution::new();
+        for part in 1..=self.part_count() {
+            context.check()?;
+            context.progress().set_phase(&format!("Part {}", part));
+            solution = solution.merge(self.solve_part(input, part, context)?);
+        }
+        return Ok(solution);
+    }
 }
@@ -1,20 +1,117 @@
-use super::challenge_config::ChallengeConfig;
+use anyhow::bail;
+pub use challenges_types::groups::GroupUnavailableError;
 
-pub trait GroupConfig {
+use super::{challenge_config::ChallengeConfig, health::GroupHealth};
+use crate::utils::is_slug;
+
+pub trait GroupConfig: Send + Sync {
     fn name(&self) -> &str;
+    /// Stable id of the group, a slug used in the API paths and to name the saved inputs and
+    /// answers
+    fn id(&self) -> String;
     fn url(&self) -> &str;
-    fn challenges(&self) -> Vec<Box<dyn ChallengeConfig>>;
-    fn challenge(&self, challenge_title: &str) -> Option<Box<dyn ChallengeConfig>> {
-        return self
-            .challenges()
+    fn challenges(&self) -> Result<Vec<Box<dyn ChallengeConfig>>, GroupUnavailableError>;
+    /// Availability of the challenges, groups that don't depend on a service are always up
+    fn health(&self) -> GroupHealth {
+        return GroupHealth::up();
+    }
+    /// Lists the challenges again instead of serving the cached listing, groups listed once
+    /// loaded have nothing to refresh
+    fn refresh(&self) {}
+    fn challenge(
+        &self,
+        challenge_title: &str,
+    ) -> Result<Option<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        return Ok(self
+            .challenges()?
+            .into_iter()
+            .find(|x| x.title() == challenge_title));
+    }
+    fn challenge_by_id(
+        &self,
+        challenge_id: &str,
+    ) -> Result<Option<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        return Ok(self
+            .challenges()?
             .into_iter()
-            .find(|x| x.title() == challenge_title);
+            .find(|x| x.id() == challenge_id));
     }
-    fn challenge_names(&self) -> Vec<String> {
-        return self
-            .challenges()
+    fn challenge_names(&self) -> Result<Vec<String>, GroupUnavailableError> {
+        return Ok(self
+            .challenges()?
             .iter()
             .map(|x| x.title().to_string())
-            .collect();
+            .collect());
+    }
+}
+
+/// Fails when the id of a listed challenge isn't a slug, which the API paths are built from,
+/// or when two of them have the same id
+pub fn check_challenge_ids<T: ChallengeConfig>(challenges: &[T]) -> anyhow::Result<()> {
+    for (i, challenge) in challenges.iter().enumerate() {
+        let id = challenge.id();
+        if !is_slug(&id) {
+            bail!(
+                "The challenge \"{}\" has the id \"{}\", which isn't made of lowercase letters \
+                 and digits separated by single dashes",
+                challenge.title(),
+                id
+            );
+        }
+        if let Some(other) = challenges[..i].iter().find(|c| c.id() == id) {
+            bail!(
+                "The challenges \"{}\" and \"{}\" have the same id {}",
+                other.title(),
+                challenge.title(),
+                id
+            );
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::{solution::Solution, solve_context::SolveContext};
+
+    /// Challenge listed with its title and id
+    struct Listed(&'static str, &'static str);
+
+    impl ChallengeConfig for Listed {
+        fn title(&self) -> &str {
+            return self.0;
+        }
+        fn id(&self) -> String {
+            return self.1.to_owned();
+        }
+        fn solve_part(
+            &self,
+            _input: &str,
+            _part: usize,
+            _context: &SolveContext,
+        ) -> anyhow::Result<Solution> {
+            return Ok(Solution::new());
+        }
+    }
+
+    #[rstest(
+        listed,
+        expected_error,
+        case(vec![("Day 1: Report Repair", "day-1"), ("Day 2: Password Philosophy", "day-2")], None),
+        case(vec![], None),
+        case(vec![("Day 1", "1"), ("Day 2", "2"), ("Day 1 again", "1")], Some("The challenges \"Day 1\" and \"Day 1 again\" have the same id 1")),
+        case(vec![("Day 1", "1"), ("???", "")], Some("The challenge \"???\" has the id \"\", which isn't made of lowercase letters and digits separated by single dashes")),
+        case(vec![("Day 1", "Day 1")], Some("The challenge \"Day 1\" has the id \"Day 1\", which isn't made of lowercase letters and digits separated by single dashes"))
+    )]
+    fn challenge_ids(listed: Vec<(&'static str, &'static str)>, expected_error: Option<&str>) {
+        let challenges: Vec<Listed> = listed.into_iter().map(|(t, i)| Listed(t, i)).collect();
+        let result = check_challenge_ids(&challenges);
+        assert_eq!(
+            result.err().map(|e| e.to_string()).as_deref(),
+            expected_error
+        );
     }
 }
@@ -1,58 +1,326 @@
+use std::thread;
+
 use super::{
-    advent_of_code_2020::config::AdventOfCode2020, api_group_config::ApiGroupConfig,
-    challenge_config::ChallengeConfig, group_config::GroupConfig,
+    api_group_config::ApiGroupConfig,
+    challenge_config::ChallengeConfig,
+    group_config::{GroupConfig, GroupUnavailableError},
+    health::GroupHealth,
+    plugin_group_config::{load_plugins, PluginGroupConfig},
+    process_group_config::ProcessGroupConfig,
+    registry::builtin_groups,
+    search::{SearchIndex, SearchResult},
+    solve_context::SolveContext,
+    wasm_group_config::WasmGroupConfig,
+};
+use crate::{
+    answers::{Answers, ChallengeVerification},
+    config::Config,
+    input_store::{ChallengeKey, InputStore},
 };
 
 pub struct GroupManager {
     groups: Vec<Box<dyn GroupConfig>>,
 }
 
+impl Default for GroupManager {
+    fn default() -> GroupManager {
+        return GroupManager::new();
+    }
+}
+
 impl GroupManager {
+    /// Groups of the default config
     pub fn new() -> GroupManager {
-        return GroupManager {
-            groups: vec![
-                Box::new(ApiGroupConfig::new(
-                    "Advent of Code 2018",
-                    "https://adventofcode.com/2018",
-                    8083,
-                    "CHALLENGES_AOC_2018_PORT",
-                )),
-                Box::new(ApiGroupConfig::new(
-                    "Advent of Code 2019",
-                    "https://adventofcode.com/2019",
-                    8082,
-                    "CHALLENGES_AOC_2019_PORT",
-                )),
-                Box::new(AdventOfCode2020::new()),
-            ],
-        };
+        return GroupManager::from_config(&Config::default());
+    }
+
+    /// Remote, command and WebAssembly groups of the config followed by its enabled built-in
+    /// groups and its plugins. The names and ids of the other groups are checked with the config,
+    /// a plugin with the name or id of a previous group is replaced by an unavailable group.
+    /// Unavailable plugins are identified by the slug of their file name, suffixed with a number
+    /// when it is taken
+    pub fn from_config(config: &Config) -> GroupManager {
+        let mut groups: Vec<Box<dyn GroupConfig>> = config
+            .remote_groups
+            .iter()
+            .map(|g| Box::new(ApiGroupConfig::new(g.clone())) as Box<dyn GroupConfig>)
+            .collect();
+        for command in &config.command_groups {
+            groups.push(Box::new(ProcessGroupConfig::new(command.clone())));
+        }
+        for module in &config.wasm_groups {
+            groups.push(Box::new(WasmGroupConfig::new(module.clone())));
+        }
+        for group in builtin_groups() {
+            if config.is_builtin_enabled(group.name) {
+                groups.push((group.create)());
+            }
+        }
+        for plugin in config.plugins_dir.iter().flat_map(|d| load_plugins(d)) {
+            let id = plugin.id();
+            let plugin = if !plugin.is_loaded() {
+                plugin
+            } else if groups.iter().any(|g| g.id() == id) {
+                PluginGroupConfig::unavailable(
+                    plugin.path(),
+                    &format!("another group has the id {}", id),
+                )
+            } else if groups.iter().any(|g| g.name() == plugin.name()) {
+                PluginGroupConfig::unavailable(
+                    plugin.path(),
+                    &format!("another group has the name {}", plugin.name()),
+                )
+            } else {
+                plugin
+            };
+            if plugin.is_loaded() {
+                groups.push(Box::new(plugin));
+            } else {
+                let id = free_id(&groups, &plugin.id());
+                groups.push(Box::new(plugin.with_id(id)));
+            }
+        }
+        return GroupManager { groups: groups };
     }
 
     pub fn get_group_names(&self) -> Vec<String> {
         return self.groups.iter().map(|x| x.name().to_string()).collect();
     }
 
-    pub fn get_group(&self, group_name: &str) -> Option<&Box<dyn GroupConfig>> {
-        return self.groups.iter().find(|x| x.name() == group_name);
+    pub fn get_group(&self, group_name: &str) -> Option<&dyn GroupConfig> {
+        return self
+            .groups
+            .iter()
+            .find(|x| x.name() == group_name)
+            .map(|x| x.as_ref());
     }
 
-    pub fn get_group_challenge_names(&self, group_name: &str) -> Option<Vec<String>> {
-        let group = self.get_group(group_name);
-        if group.is_some() {
-            return Some(group.unwrap().challenge_names());
-        }
-        return None;
+    pub fn get_group_by_id(&self, group_id: &str) -> Option<&dyn GroupConfig> {
+        return self
+            .groups
+            .iter()
+            .find(|x| x.id() == group_id)
+            .map(|x| x.as_ref());
+    }
+
+    pub fn get_group_health(&self, group_name: &str) -> Option<GroupHealth> {
+        return self.get_group(group_name).map(|g| g.health());
     }
 
+    /// Challenge names of the group, `None` if the group doesn't exist
+    pub fn get_group_challenge_names(
+        &self,
+        group_name: &str,
+    ) -> Result<Option<Vec<String>>, GroupUnavailableError> {
+        return self
+            .get_group(group_name)
+            .map(|g| g.challenge_names())
+            .transpose();
+    }
+
+    /// Challenge of the group, `None` if the group or the challenge doesn't exist
     pub fn get_challenge(
         &self,
         group_name: &str,
         challenge_name: &str,
-    ) -> Option<Box<dyn ChallengeConfig>> {
-        let group = self.get_group(group_name);
-        if group.is_some() {
-            return group.unwrap().challenge(challenge_name);
+    ) -> Result<Option<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        return match self.get_group(group_name) {
+            Some(g) => g.challenge(challenge_name),
+            None => Ok(None),
+        };
+    }
+
+    /// Lists the challenges of every group again, the groups are refreshed in parallel
+    pub fn refresh(&self) {
+        thread::scope(|scope| {
+            for group in self.groups.iter() {
+                scope.spawn(move || group.refresh());
+            }
+        });
+    }
+
+    /// Index of the challenges of the available groups
+    pub fn search_index(&self) -> SearchIndex {
+        let mut index = SearchIndex::new();
+        for group in self.groups.iter() {
+            for challenge in group.challenges().into_iter().flatten() {
+                index.add(group.as_ref(), challenge.as_ref());
+            }
+        }
+        return index;
+    }
+
+    /// Challenges of the available groups matching the query, see `SearchIndex::search`
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        return self.search_index().search(query);
+    }
+
+    /// Solves every challenge that has a saved input, optionally limited to a single group,
+    /// and compares the results with the known answers. Each solve gets a context from `context`.
+    /// Unavailable groups are skipped when verifying all the groups
+    pub fn verify(
+        &self,
+        store: &InputStore,
+        answers: &Answers,
+        group_name: Option<&str>,
+        context: &dyn Fn() -> SolveContext,
+    ) -> Result<Vec<ChallengeVerification>, GroupUnavailableError> {
+        let mut verifications = vec![];
+        for group in self.groups.iter() {
+            if group_name.is_some() && group_name != Some(group.name()) {
+                continue;
+            }
+            let challenges = match group.challenges() {
+                Ok(c) => c,
+                Err(e) if group_name.is_some() => return Err(e),
+                Err(_) => continue,
+            };
+            for challenge in challenges {
+                let key = ChallengeKey::new(group.as_ref(), challenge.as_ref());
+                let input = match store.load(&key) {
+                    Ok(Some(i)) => i,
+                    _ => continue,
+                };
+                verifications.push(ChallengeVerification::new(
+                    group.name(),
+                    challenge.title(),
+                    challenge.solve(&input, &context()),
+                    answers.expected(&key),
+                ));
+            }
         }
-        return None;
+        return Ok(verifications);
+    }
+}
+
+/// The id, or the id suffixed with the first number making it unique among the groups. An empty
+/// id becomes `plugin`
+fn free_id(groups: &[Box<dyn GroupConfig>], id: &str) -> String {
+    let id = if id.is_empty() { "plugin" } else { id };
+    let taken = |candidate: &str| groups.iter().any(|g| g.id() == candidate);
+    if !taken(id) {
+        return id.to_owned();
+    }
+    return (2..)
+        .map(|n| format!("{}-{}", id, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, env::consts::DLL_EXTENSION, fs, net::TcpListener, path::Path, process};
+
+    use super::*;
+    use crate::{
+        config::RemoteGroupConfig,
+        groups::{
+            advent_of_code_2020::config::AdventOfCode2020, health::HealthStatus,
+            stub_server::stub_command,
+        },
+        utils::slugify,
+    };
+
+    /// Manager with the 2020 group, a command group and a remote group whose service is down
+    fn manager() -> GroupManager {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        return GroupManager::from_config(&Config {
+            builtin_groups: None,
+            remote_groups: vec![RemoteGroupConfig {
+                name: "Down".to_owned(),
+                base_url: format!("http://127.0.0.1:{}", port),
+                timeout_secs: 5,
+                cache_ttl_secs: 60,
+                ..RemoteGroupConfig::default()
+            }],
+            command_groups: vec![stub_command(5)],
+            wasm_groups: vec![],
+            plugins_dir: None,
+        });
+    }
+
+    #[test]
+    fn unavailable_group() {
+        let manager = manager();
+        let error = manager.get_group_challenge_names("Down").unwrap_err();
+        assert_eq!(error.group, "Down");
+        assert!(manager.get_challenge("Down", "Day 1").is_err());
+        assert_eq!(
+            manager.get_group_health("Down").unwrap().status,
+            HealthStatus::Down
+        );
+        assert_eq!(
+            manager.get_group_names(),
+            vec!["Down", "Script", AdventOfCode2020::NAME]
+        );
+        assert_eq!(
+            manager.get_group_challenge_names("Script").unwrap(),
+            Some(vec!["Day 1".to_owned()])
+        );
+        assert!(manager
+            .get_group_challenge_names(AdventOfCode2020::NAME)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            manager
+                .get_group_by_id("advent-of-code-2020")
+                .unwrap()
+                .name(),
+            AdventOfCode2020::NAME
+        );
+        assert!(manager.get_group_by_id("Down").is_none());
+        assert!(manager
+            .get_group_challenge_names("Unknown")
+            .unwrap()
+            .is_none());
+
+        let results = manager.search("day 1");
+        assert_eq!(results[0].group, "Script");
+        assert!(results.iter().all(|r| r.group != "Down"));
+
+        let store = InputStore::new(Path::new("missing"));
+        let answers = Answers::default();
+        assert!(manager
+            .verify(&store, &answers, None, &SolveContext::default)
+            .is_ok());
+        assert!(manager
+            .verify(&store, &answers, Some("Down"), &SolveContext::default)
+            .is_err());
+    }
+
+    #[test]
+    fn unavailable_plugin_ids() {
+        let dir = env::temp_dir().join(format!("challenges-manager-plugins-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in &["broken", "broken_"] {
+            fs::write(dir.join(format!("{}.{}", name, DLL_EXTENSION)), "").unwrap();
+        }
+        let taken = slugify(&format!("broken.{}", DLL_EXTENSION));
+        let manager = GroupManager::from_config(&Config {
+            builtin_groups: Some(vec![]),
+            remote_groups: vec![RemoteGroupConfig {
+                name: "Remote".to_owned(),
+                id: Some(taken.clone()),
+                base_url: "http://127.0.0.1:1".to_owned(),
+                ..RemoteGroupConfig::default()
+            }],
+            command_groups: vec![],
+            wasm_groups: vec![],
+            plugins_dir: Some(dir.clone()),
+        });
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            manager.groups.iter().map(|g| g.id()).collect::<Vec<_>>(),
+            vec![
+                taken.clone(),
+                format!("{}-2", taken),
+                format!("{}-3", taken)
+            ]
+        );
     }
 }
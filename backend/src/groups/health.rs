@@ -0,0 +1 @@
+pub use challenges_types::health::{GroupHealth, HealthStatus};
@@ -1,7 +1,26 @@
+#[macro_use]
+pub mod registry;
+
+pub mod cancellation;
 pub mod challenge_config;
 pub mod group_config;
 pub mod group_manager;
+pub mod health;
+pub mod plugin;
+pub mod process_client;
+pub mod progress;
+pub mod remote_client;
+pub mod remote_protocol;
+pub mod search;
+pub mod solution;
+pub mod solve_context;
+
+#[cfg(test)]
+mod stub_server;
 
 // Groups
 mod advent_of_code_2020;
 mod api_group_config;
+mod plugin_group_config;
+mod process_group_config;
+mod wasm_group_config;
@@ -0,0 +1,147 @@
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_void},
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use super::{
+    cancellation::CancellationToken,
+    group_config::GroupConfig,
+    remote_protocol::{
+        ListResponse, ListedChallenge, ProcessRequest, SolveResponse, SolveStatus, PROTOCOL_VERSION,
+    },
+    solve_context::SolveContext,
+};
+
+/// Version of `PluginApi`, plugins built for another version are refused
+pub const PLUGIN_ABI_VERSION: u32 = 2;
+
+/// Symbol of the `extern "C" fn() -> PluginApi` exported by plugins, see `export_group!`
+pub const PLUGIN_ENTRY_POINT: &[u8] = b"challenges_plugin\0";
+
+/// C interface of a group compiled as a `cdylib`. Requests and answers are the JSON messages of
+/// command groups (`ProcessRequest`), as NUL-terminated UTF-8 strings, so plugins don't depend
+/// on the layout of the Rust types of the backend
+#[repr(C)]
+pub struct PluginApi {
+    /// `PLUGIN_ABI_VERSION` of the plugin, always the first field
+    pub abi_version: u32,
+    /// Passed back to `handle`
+    pub context: *const c_void,
+    /// Name, id and URL of the group, valid as long as the plugin is loaded
+    pub name: *const c_char,
+    pub id: *const c_char,
+    pub url: *const c_char,
+    /// Answers a request, the answer is released by `free`. Called from any thread
+    pub handle: unsafe extern "C" fn(context: *const c_void, request: *const c_char) -> *mut c_char,
+    pub free: unsafe extern "C" fn(answer: *mut c_char),
+}
+
+impl PluginApi {
+    /// Interface of a plugin serving the group, which is kept until the process exits
+    pub fn export(group: Box<dyn GroupConfig>) -> PluginApi {
+        let group: &'static dyn GroupConfig = Box::leak(group);
+        let name = CString::new(group.name()).unwrap_or_default();
+        let id = CString::new(group.id()).unwrap_or_default();
+        let url = CString::new(group.url()).unwrap_or_default();
+        return PluginApi {
+            abi_version: PLUGIN_ABI_VERSION,
+            context: Box::into_raw(Box::new(group)) as *const c_void,
+            name: name.into_raw(),
+            id: id.into_raw(),
+            url: url.into_raw(),
+            handle: handle_exported,
+            free: free_exported,
+        };
+    }
+}
+
+unsafe extern "C" fn handle_exported(
+    context: *const c_void,
+    request: *const c_char,
+) -> *mut c_char {
+    let group = *(context as *const &'static dyn GroupConfig);
+    let request = CStr::from_ptr(request).to_string_lossy();
+    // Unwinding out of an extern "C" function aborts the whole backend
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| handle_request(group, &request)))
+        .unwrap_or_else(|_| solve_error("The plugin panicked"));
+    return CString::new(answer).unwrap_or_default().into_raw();
+}
+
+unsafe extern "C" fn free_exported(answer: *mut c_char) {
+    if !answer.is_null() {
+        drop(CString::from_raw(answer));
+    }
+}
+
+fn solve_error(message: &str) -> String {
+    let response = SolveResponse {
+        version: PROTOCOL_VERSION,
+        status: SolveStatus::Error,
+        parts: vec![],
+        error: Some(message.to_owned()),
+        duration_ms: None,
+    };
+    return serde_json::to_string(&response).unwrap();
+}
+
+/// Answers a request of the JSON protocol with the challenges of the group, identified by their
+/// id
+pub fn handle_request(group: &dyn GroupConfig, request: &str) -> String {
+    return match serde_json::from_str::<ProcessRequest>(request) {
+        Ok(ProcessRequest::List { .. }) => {
+            let response = match group.challenges() {
+                Ok(challenges) => ListResponse {
+                    version: PROTOCOL_VERSION,
+                    challenges: challenges
+                        .iter()
+                        .map(|c| ListedChallenge {
+                            id: c.id(),
+                            title: c.title().to_owned(),
+                            description: c.description().to_owned(),
+                            tags: c.tags().iter().map(|t| (*t).to_owned()).collect(),
+                        })
+                        .collect(),
+                    error: None,
+                },
+                Err(e) => ListResponse {
+                    version: PROTOCOL_VERSION,
+                    challenges: vec![],
+                    error: Some(e.message),
+                },
+            };
+            serde_json::to_string(&response).unwrap()
+        }
+        Ok(ProcessRequest::Solve { id, request }) => {
+            let challenge = match group.challenge_by_id(&id) {
+                Ok(Some(c)) => c,
+                Ok(None) => return solve_error(&format!("Unknown challenge {}", id)),
+                Err(e) => return solve_error(&e.to_string()),
+            };
+            let token = match request.timeout_ms {
+                Some(t) => CancellationToken::with_timeout(Duration::from_millis(t)),
+                None => CancellationToken::new(),
+            };
+            let start = Instant::now();
+            let result = challenge.solve(&request.input, &SolveContext::new(token));
+            serde_json::to_string(&SolveResponse::from_result(&result, start.elapsed())).unwrap()
+        }
+        Err(e) => solve_error(&format!("Invalid request: {}", e)),
+    };
+}
+
+/// Exports the group returned by the constructor as the plugin of a `cdylib` crate:
+///
+/// ```ignore
+/// challenges::export_group!(MyGroup::new);
+/// ```
+#[macro_export]
+macro_rules! export_group {
+    ($constructor:expr) => {
+        #[no_mangle]
+        pub extern "C" fn challenges_plugin() -> $crate::groups::plugin::PluginApi {
+            return $crate::groups::plugin::PluginApi::export(Box::new(($constructor)()));
+        }
+    };
+}
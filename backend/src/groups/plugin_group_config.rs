@@ -0,0 +1,391 @@
+use std::{
+    env::consts::DLL_EXTENSION,
+    ffi::{CStr, CString},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{anyhow, bail, Result};
+use libloading::{Library, Symbol};
+use serde::de::DeserializeOwned;
+
+use super::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    group_config::{check_challenge_ids, GroupConfig, GroupUnavailableError},
+    health::{GroupHealth, HealthStatus},
+    plugin::{PluginApi, PLUGIN_ABI_VERSION, PLUGIN_ENTRY_POINT},
+    remote_protocol::{ListResponse, ProcessRequest, SolveResponse, PROTOCOL_VERSION},
+    solution::Solution,
+    solve_context::SolveContext,
+};
+use crate::utils::{is_slug, slugify};
+
+/// Function exported by plugins as `PLUGIN_ENTRY_POINT`
+type EntryPoint = unsafe extern "C" fn() -> PluginApi;
+
+/// Plugin loaded in the backend
+struct Plugin {
+    api: PluginApi,
+    name: String,
+    id: String,
+    url: String,
+    // The API points into the library, which is never unloaded while the plugin is in use
+    _library: Option<Library>,
+}
+
+// Plugins answer from any thread, as required by `PluginApi::handle`
+unsafe impl Send for Plugin {}
+unsafe impl Sync for Plugin {}
+
+impl Plugin {
+    fn load(path: &Path) -> Result<Plugin> {
+        unsafe {
+            let library = Library::new(path)?;
+            let api = {
+                let entry_point: Symbol<EntryPoint> = library.get(PLUGIN_ENTRY_POINT)?;
+                entry_point()
+            };
+            return Plugin::new(api, Some(library));
+        }
+    }
+
+    /// Checks the API returned by the entry point of a plugin
+    unsafe fn new(api: PluginApi, library: Option<Library>) -> Result<Plugin> {
+        if api.abi_version != PLUGIN_ABI_VERSION {
+            bail!(
+                "Unsupported ABI version {}, expected {}",
+                api.abi_version,
+                PLUGIN_ABI_VERSION
+            );
+        }
+        if api.name.is_null() || api.id.is_null() || api.url.is_null() {
+            bail!("The plugin has no name, id or URL");
+        }
+        let name = CStr::from_ptr(api.name).to_string_lossy().into_owned();
+        let id = CStr::from_ptr(api.id).to_string_lossy().into_owned();
+        let url = CStr::from_ptr(api.url).to_string_lossy().into_owned();
+        if name.trim().is_empty() {
+            bail!("The plugin has an empty name");
+        }
+        if !is_slug(&id) {
+            bail!(
+                "The plugin has the id \"{}\", which isn't made of lowercase letters and digits \
+                 separated by single dashes",
+                id
+            );
+        }
+        return Ok(Plugin {
+            api: api,
+            name: name,
+            id: id,
+            url: url,
+            _library: library,
+        });
+    }
+
+    fn request<R: DeserializeOwned>(&self, request: &ProcessRequest) -> Result<R> {
+        let request = CString::new(serde_json::to_string(request)?)?;
+        let answer = unsafe {
+            let raw = (self.api.handle)(self.api.context, request.as_ptr());
+            if raw.is_null() {
+                bail!("The plugin did not answer");
+            }
+            let answer = CStr::from_ptr(raw).to_string_lossy().into_owned();
+            (self.api.free)(raw);
+            answer
+        };
+        return serde_json::from_str(&answer)
+            .map_err(|e| anyhow!("Invalid answer \"{}\": {}", answer, e));
+    }
+}
+
+#[derive(Clone)]
+pub struct PluginChallenge {
+    id: String,
+    title: String,
+    description: String,
+    tags: Vec<String>,
+    plugin: Arc<Plugin>,
+}
+
+impl ChallengeConfig for PluginChallenge {
+    fn title(&self) -> &str {
+        return self.title.as_str();
+    }
+
+    fn id(&self) -> String {
+        return self.id.clone();
+    }
+
+    fn description(&self) -> &str {
+        return self.description.as_str();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return self.tags.iter().map(String::as_str).collect();
+    }
+
+    fn solve_part(
+        &self,
+        input: &str,
+        part: usize,
+        context: &SolveContext,
+    ) -> anyhow::Result<Solution> {
+        // Plugins always solve all parts, only keep the requested one
+        let solution = self.solve(input, context)?;
+        return match solution.nth_part(part) {
+            Some(s) => Ok(s),
+            None => Err(ChallengeError::invalid_part(part).into()),
+        };
+    }
+
+    fn solves_parts_together(&self) -> bool {
+        return true;
+    }
+
+    /// The plugin can't be interrupted, it only gets the time left before the deadline
+    fn solve(&self, input: &str, context: &SolveContext) -> anyhow::Result<Solution> {
+        let token = context.token();
+        token.check()?;
+        let timeout_ms = token.remaining().map(|r| r.as_millis() as u64);
+        let request = ProcessRequest::solve(&self.id, input, timeout_ms);
+        let response: SolveResponse = self.plugin.request(&request)?;
+        return Ok(response.into_solution()?);
+    }
+}
+
+fn list_challenges(plugin: &Arc<Plugin>) -> Result<Vec<PluginChallenge>> {
+    let response: ListResponse = plugin.request(&ProcessRequest::list())?;
+    if response.version != PROTOCOL_VERSION {
+        bail!(
+            "Unsupported protocol version {}, expected {}",
+            response.version,
+            PROTOCOL_VERSION
+        );
+    }
+    if let Some(error) = response.error {
+        bail!(error);
+    }
+    let challenges: Vec<PluginChallenge> = response
+        .challenges
+        .into_iter()
+        .map(|c| PluginChallenge {
+            id: c.id,
+            title: c.title,
+            description: c.description,
+            tags: c.tags,
+            plugin: plugin.clone(),
+        })
+        .collect();
+    check_challenge_ids(&challenges)?;
+    return Ok(challenges);
+}
+
+/// Group of a plugin, listed once loaded. A plugin that can't be loaded is an unavailable group
+/// named after its file
+pub struct PluginGroupConfig {
+    name: String,
+    id: String,
+    url: String,
+    path: PathBuf,
+    challenges: Result<Vec<PluginChallenge>, String>,
+}
+
+impl PluginGroupConfig {
+    pub fn load(path: &Path) -> Self {
+        let plugin = Plugin::load(path).map(Arc::new);
+        return PluginGroupConfig::from_plugin(path, plugin);
+    }
+
+    fn from_plugin(path: &Path, plugin: Result<Arc<Plugin>>) -> Self {
+        return match plugin {
+            Ok(plugin) => PluginGroupConfig {
+                name: plugin.name.clone(),
+                id: plugin.id.clone(),
+                url: plugin.url.clone(),
+                path: path.to_owned(),
+                challenges: list_challenges(&plugin).map_err(|e| e.to_string()),
+            },
+            Err(e) => PluginGroupConfig::unavailable(path, &e.to_string()),
+        };
+    }
+
+    /// Group named after the plugin file whose challenges can't be listed
+    pub fn unavailable(path: &Path, message: &str) -> Self {
+        let name = path
+            .file_name()
+            .map_or(path.to_string_lossy(), |n| n.to_string_lossy());
+        return PluginGroupConfig {
+            id: slugify(&name),
+            name: name.into_owned(),
+            url: String::new(),
+            path: path.to_owned(),
+            challenges: Err(format!("Unable to load {}: {}", path.display(), message)),
+        };
+    }
+
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+
+    /// Whether the challenges of the plugin were listed
+    pub fn is_loaded(&self) -> bool {
+        return self.challenges.is_ok();
+    }
+
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = id;
+        return self;
+    }
+}
+
+impl GroupConfig for PluginGroupConfig {
+    fn name(&self) -> &str {
+        return self.name.as_str();
+    }
+    fn id(&self) -> String {
+        return self.id.clone();
+    }
+    fn url(&self) -> &str {
+        return self.url.as_str();
+    }
+
+    fn challenges(&self) -> Result<Vec<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        return match &self.challenges {
+            Ok(challenges) => Ok(challenges
+                .iter()
+                .map(|e| Box::new(e.clone()) as Box<dyn ChallengeConfig>)
+                .collect()),
+            Err(e) => Err(GroupUnavailableError::new(self.name(), e)),
+        };
+    }
+
+    fn health(&self) -> GroupHealth {
+        return match &self.challenges {
+            Ok(_) => GroupHealth::up(),
+            Err(e) => GroupHealth {
+                status: HealthStatus::Down,
+                last_error: Some(e.clone()),
+            },
+        };
+    }
+}
+
+/// Loads the plugins of the directory, in the order of their file names
+pub fn load_plugins(dir: &Path) -> Vec<PluginGroupConfig> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == DLL_EXTENSION))
+        .collect();
+    paths.sort();
+    return paths.iter().map(|p| PluginGroupConfig::load(p)).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::{self, consts::DLL_PREFIX};
+
+    use super::*;
+    use crate::groups::{
+        advent_of_code_2020::config::AdventOfCode2020, challenge_config::ChallengeErrorKind,
+        remote_protocol::SolveStatus,
+    };
+
+    /// Plugin exporting the 2020 group without going through a library
+    fn exported_plugin() -> Arc<Plugin> {
+        let api = PluginApi::export(Box::new(AdventOfCode2020::new()));
+        return Arc::new(unsafe { Plugin::new(api, None) }.unwrap());
+    }
+
+    #[test]
+    fn exported_group() {
+        let group = PluginGroupConfig::from_plugin(Path::new("2020.so"), Ok(exported_plugin()));
+        assert_eq!(group.name(), AdventOfCode2020::NAME);
+        assert_eq!(group.id(), AdventOfCode2020::ID);
+        assert_eq!(group.health(), GroupHealth::up());
+        assert_eq!(
+            group.challenge_names().unwrap(),
+            AdventOfCode2020::new().challenge_names().unwrap()
+        );
+        let challenge = group
+            .challenge_by_id("day-1-report-repair")
+            .unwrap()
+            .unwrap();
+        let solution = challenge
+            .solve_part(
+                "1721\n979\n366\n299\n675\n1456",
+                2,
+                &SolveContext::default(),
+            )
+            .unwrap();
+        assert_eq!(solution, Solution::new().with_part("Part 2", 241861950));
+        let error = challenge
+            .solve("abc", &SolveContext::default())
+            .unwrap_err()
+            .downcast::<ChallengeError>()
+            .unwrap();
+        assert_eq!(error.kind(), ChallengeErrorKind::Failed);
+    }
+
+    #[test]
+    fn invalid_requests() {
+        let plugin = exported_plugin();
+        let response: SolveResponse = plugin
+            .request(&ProcessRequest::solve("day-0", "", None))
+            .unwrap();
+        assert_eq!(response.error.as_deref(), Some("Unknown challenge day-0"));
+        let response: SolveResponse = plugin
+            .request(&ProcessRequest::solve("day-1-report-repair", "", Some(0)))
+            .unwrap();
+        assert_eq!(response.status, SolveStatus::TimedOut);
+    }
+
+    #[test]
+    fn unavailable() {
+        let dir = env::temp_dir().join(format!("challenges-plugins-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("invalid.{}", DLL_EXTENSION));
+        fs::write(&path, "not a library").unwrap();
+        fs::write(dir.join("ignored.txt"), "").unwrap();
+        let groups = load_plugins(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name(), format!("invalid.{}", DLL_EXTENSION));
+        assert_eq!(groups[0].path(), path);
+        let error = groups[0].challenges().err().unwrap();
+        assert!(error.message.starts_with("Unable to load"), "{}", error);
+        assert_eq!(groups[0].health().status, HealthStatus::Down);
+    }
+
+    /// Loads the `plugin` example, built along the tests
+    #[test]
+    fn example_plugin() {
+        let examples_dir = env::current_exe()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .with_file_name("examples");
+        let path = examples_dir.join(format!("{}plugin.{}", DLL_PREFIX, DLL_EXTENSION));
+        let group = PluginGroupConfig::load(&path);
+        assert_eq!(
+            group.name(),
+            "Example Plugin",
+            "{:?}",
+            group.challenges().err()
+        );
+        assert_eq!(group.id(), "example-plugin");
+        let challenge = group.challenge_by_id("sum").unwrap().unwrap();
+        let solution = challenge.solve("1 2 3", &SolveContext::default()).unwrap();
+        assert_eq!(
+            solution,
+            Solution::new()
+                .with_part("Part 1", 6)
+                .with_part("Part 2", 6)
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, ExitStatus, Stdio},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+
+use super::{
+    cancellation::CancellationToken, challenge_config::ChallengeError,
+    remote_protocol::ProcessRequest,
+};
+use crate::config::CommandGroupConfig;
+
+/// Interval at which a pending request checks its cancellation token
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Time given to a process that closed its output to exit and flush its errors
+const EXIT_GRACE: Duration = Duration::from_millis(200);
+
+/// Running process of a command group
+struct Session {
+    child: Arc<Mutex<Child>>,
+    stdin: ChildStdin,
+    lines: Receiver<std::io::Result<String>>,
+    /// Last line written to the standard error, sent once it is closed
+    stderr: Receiver<String>,
+}
+
+impl Session {
+    /// Error describing why the process stopped answering
+    fn exited(&mut self) -> anyhow::Error {
+        let start = Instant::now();
+        let mut status = self.try_wait();
+        while status.is_none() && start.elapsed() < EXIT_GRACE {
+            thread::sleep(Duration::from_millis(10));
+            status = self.try_wait();
+        }
+        let message = match status {
+            Some(s) => format!("The process exited ({})", s),
+            None => "The process closed its output".to_owned(),
+        };
+        return match self.stderr.recv_timeout(EXIT_GRACE) {
+            Ok(line) if !line.is_empty() => anyhow!("{}: {}", message, line),
+            _ => anyhow!(message),
+        };
+    }
+
+    fn try_wait(&self) -> Option<ExitStatus> {
+        return self.child.lock().unwrap().try_wait().ok().flatten();
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let mut child = self.child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// Process of a command group, started on the first request and restarted after a failure.
+/// Requests are sent one at a time, each being answered by a single line
+pub struct ProcessClient {
+    command: CommandGroupConfig,
+    session: Mutex<Option<Session>>,
+    /// Process of the session, which can be stopped while a request holds the session
+    child: Mutex<Option<Arc<Mutex<Child>>>>,
+    last_error: Mutex<Option<String>>,
+}
+
+impl ProcessClient {
+    pub fn new(command: CommandGroupConfig) -> Self {
+        return ProcessClient {
+            command: command,
+            session: Mutex::new(None),
+            child: Mutex::new(None),
+            last_error: Mutex::new(None),
+        };
+    }
+
+    pub fn command(&self) -> &CommandGroupConfig {
+        return &self.command;
+    }
+
+    /// Why the last request failed, cleared by the next successful one. Cancelled and timed
+    /// out requests aren't failures of the process
+    pub fn last_error(&self) -> Option<String> {
+        return self.last_error.lock().unwrap().clone();
+    }
+
+    /// Stops the process without waiting for the pending request, which fails.
+    /// The next request starts it again
+    pub fn stop(&self) {
+        let child = self.child.lock().unwrap().take();
+        if let Some(c) = child {
+            let _ = c.lock().unwrap().kill();
+        }
+    }
+
+    /// Whether the process of the session wasn't stopped
+    fn is_running(&self, session: &Session) -> bool {
+        return self
+            .child
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|c| Arc::ptr_eq(c, &session.child));
+    }
+
+    /// Sends the request and waits for its answer, until the token stops or `timeout_secs`.
+    /// The process is stopped when it doesn't answer, as a late answer can't be told apart
+    /// from the answer to the next request
+    pub fn request<R: DeserializeOwned>(
+        &self,
+        request: &ProcessRequest,
+        token: &CancellationToken,
+    ) -> Result<R> {
+        let mut session = self.session.lock().unwrap();
+        if session.as_ref().is_some_and(|s| !self.is_running(s)) {
+            *session = None;
+        }
+        let result = match session.as_mut() {
+            Some(s) => self.exchange(s, request, token),
+            None => self.spawn().and_then(|mut s| {
+                let result = self.exchange(&mut s, request, token);
+                *session = Some(s);
+                result
+            }),
+        };
+        match &result {
+            Ok(_) => *self.last_error.lock().unwrap() = None,
+            Err(e) => {
+                let stopped = session.as_ref().is_some_and(|s| !self.is_running(s));
+                *session = None;
+                if !stopped && !e.is::<ChallengeError>() {
+                    *self.last_error.lock().unwrap() = Some(e.to_string());
+                }
+            }
+        }
+        return result;
+    }
+
+    fn spawn(&self) -> Result<Session> {
+        let mut command = Command::new(&self.command.command);
+        command
+            .args(&self.command.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = &self.command.working_dir {
+            command.current_dir(dir);
+        }
+        let mut child = match command.spawn() {
+            Ok(c) => c,
+            Err(e) => return Err(anyhow!("Unable to start {}: {}", self.command.command, e)),
+        };
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let stderr = child.stderr.take().unwrap();
+
+        let (line_sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let failed = line.is_err();
+                if line_sender.send(line).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        let (stderr_sender, stderr_receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut last = String::new();
+            let mut lines = BufReader::new(stderr).lines();
+            while let Some(Ok(line)) = lines.next() {
+                if !line.trim().is_empty() {
+                    last = line.trim().to_owned();
+                }
+            }
+            let _ = stderr_sender.send(last);
+        });
+        let child = Arc::new(Mutex::new(child));
+        *self.child.lock().unwrap() = Some(child.clone());
+        return Ok(Session {
+            child: child,
+            stdin: stdin,
+            lines: lines,
+            stderr: stderr_receiver,
+        });
+    }
+
+    fn exchange<R: DeserializeOwned>(
+        &self,
+        session: &mut Session,
+        request: &ProcessRequest,
+        token: &CancellationToken,
+    ) -> Result<R> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        let written = session
+            .stdin
+            .write_all(line.as_bytes())
+            .and_then(|_| session.stdin.flush());
+        if written.is_err() {
+            return Err(session.exited());
+        }
+        let timeout = Duration::from_secs(self.command.timeout_secs);
+        let deadline = Instant::now() + token.remaining().map_or(timeout, |r| r.min(timeout));
+        loop {
+            token.check()?;
+            if !self.is_running(session) {
+                return Err(anyhow!("The process was stopped"));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining == Duration::from_secs(0) {
+                return Err(ChallengeError::timed_out().into());
+            }
+            match session.lines.recv_timeout(remaining.min(POLL_INTERVAL)) {
+                Ok(Ok(line)) if line.trim().is_empty() => {}
+                Ok(Ok(line)) => {
+                    return serde_json::from_str(&line)
+                        .map_err(|e| anyhow!("Invalid answer \"{}\": {}", line.trim(), e));
+                }
+                Ok(Err(e)) => return Err(e.into()),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return Err(session.exited()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::{
+        challenge_config::ChallengeErrorKind,
+        remote_protocol::{ListResponse, SolveResponse, SolveStatus},
+        stub_server::stub_command,
+    };
+
+    fn solve(
+        client: &ProcessClient,
+        input: &str,
+        token: &CancellationToken,
+    ) -> Result<SolveResponse> {
+        return client.request(&ProcessRequest::solve("1", input, None), token);
+    }
+
+    #[test]
+    fn list_and_solve() {
+        let client = ProcessClient::new(stub_command(5));
+        let listing: ListResponse = client
+            .request(&ProcessRequest::list(), &CancellationToken::new())
+            .unwrap();
+        assert_eq!(listing.challenges[0].title, "Day 1");
+        let response = solve(&client, "input", &CancellationToken::new()).unwrap();
+        assert_eq!(response.status, SolveStatus::Ok);
+        let response = solve(&client, "fail", &CancellationToken::new()).unwrap();
+        assert_eq!(response.error.as_deref(), Some("Invalid input"));
+        assert_eq!(client.last_error(), None);
+    }
+
+    #[rstest(
+        input,
+        expected_error,
+        case("crash", "The process exited (exit status: 3): oops"),
+        case(
+            "garbage",
+            "Invalid answer \"garbage\": expected value at line 1 column 1"
+        )
+    )]
+    fn restarted_after_failure(input: &str, expected_error: &str) {
+        let client = ProcessClient::new(stub_command(5));
+        let error = solve(&client, input, &CancellationToken::new()).unwrap_err();
+        assert_eq!(error.to_string(), expected_error);
+        assert_eq!(client.last_error().as_deref(), Some(expected_error));
+        assert!(solve(&client, "input", &CancellationToken::new()).is_ok());
+        assert_eq!(client.last_error(), None);
+    }
+
+    #[rstest(
+        timeout_secs,
+        token,
+        expected_kind,
+        case(1, CancellationToken::new(), ChallengeErrorKind::TimedOut),
+        case(
+            5,
+            CancellationToken::with_timeout(Duration::from_millis(100)),
+            ChallengeErrorKind::TimedOut
+        )
+    )]
+    fn unanswered(timeout_secs: u64, token: CancellationToken, expected_kind: ChallengeErrorKind) {
+        let client = ProcessClient::new(stub_command(timeout_secs));
+        let start = Instant::now();
+        let error = solve(&client, "hang", &token).unwrap_err();
+        assert_eq!(
+            error.downcast::<ChallengeError>().unwrap().kind(),
+            expected_kind
+        );
+        assert!(start.elapsed() < Duration::from_secs(3));
+        assert_eq!(client.last_error(), None);
+        assert!(solve(&client, "input", &CancellationToken::new()).is_ok());
+    }
+
+    #[test]
+    fn cancelled() {
+        let client = ProcessClient::new(stub_command(5));
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            canceller.cancel();
+        });
+        let error = solve(&client, "hang", &token).unwrap_err();
+        let error = error.downcast::<ChallengeError>().unwrap();
+        assert_eq!(error.kind(), ChallengeErrorKind::Cancelled);
+    }
+
+    #[test]
+    fn stopped() {
+        let client = Arc::new(ProcessClient::new(stub_command(5)));
+        assert!(solve(&client, "input", &CancellationToken::new()).is_ok());
+        client.stop();
+        assert!(solve(&client, "input", &CancellationToken::new()).is_ok());
+
+        let stopper = client.clone();
+        let stopping = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            let start = Instant::now();
+            stopper.stop();
+            return start.elapsed();
+        });
+        let start = Instant::now();
+        let error = solve(&client, "hang", &CancellationToken::new()).unwrap_err();
+        assert_eq!(error.to_string(), "The process was stopped");
+        assert!(start.elapsed() < Duration::from_secs(3));
+        assert!(stopping.join().unwrap() < Duration::from_millis(100));
+        assert_eq!(client.last_error(), None);
+        assert!(solve(&client, "input", &CancellationToken::new()).is_ok());
+    }
+
+    #[test]
+    fn missing_command() {
+        let client = ProcessClient::new(CommandGroupConfig {
+            command: "./missing-command".to_owned(),
+            ..stub_command(5)
+        });
+        let error = solve(&client, "input", &CancellationToken::new()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Unable to start ./missing-command"));
+        assert!(client.last_error().is_some());
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+
+use super::{
+    cancellation::CancellationToken,
+    challenge_config::{ChallengeConfig, ChallengeError, ChallengeErrorKind},
+    group_config::{check_challenge_ids, GroupConfig, GroupUnavailableError},
+    health::{GroupHealth, HealthStatus},
+    process_client::ProcessClient,
+    remote_protocol::{ListResponse, ProcessRequest, SolveResponse, PROTOCOL_VERSION},
+    solution::Solution,
+    solve_context::SolveContext,
+};
+use crate::config::CommandGroupConfig;
+
+/// Time before a failed listing is attempted again, so that a broken command isn't started on
+/// every request
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+#[derive(Clone)]
+pub struct ProcessChallenge {
+    id: String,
+    title: String,
+    description: String,
+    tags: Vec<String>,
+    client: Arc<ProcessClient>,
+}
+
+impl ChallengeConfig for ProcessChallenge {
+    fn title(&self) -> &str {
+        return self.title.as_str();
+    }
+
+    fn id(&self) -> String {
+        return self.id.clone();
+    }
+
+    fn description(&self) -> &str {
+        return self.description.as_str();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return self.tags.iter().map(String::as_str).collect();
+    }
+
+    fn solve_part(
+        &self,
+        input: &str,
+        part: usize,
+        context: &SolveContext,
+    ) -> anyhow::Result<Solution> {
+        // Processes always solve all parts, only keep the requested one
+        let solution = self.solve(input, context)?;
+        return match solution.nth_part(part) {
+            Some(s) => Ok(s),
+            None => Err(ChallengeError::invalid_part(part).into()),
+        };
+    }
+
+    fn solves_parts_together(&self) -> bool {
+        return true;
+    }
+
+    fn solve(&self, input: &str, context: &SolveContext) -> anyhow::Result<Solution> {
+        let token = context.token();
+        token.check()?;
+        let timeout_ms = token.remaining().map(|r| r.as_millis() as u64);
+        let request = ProcessRequest::solve(&self.id, input, timeout_ms);
+        let response: SolveResponse = self.client.request(&request, token)?;
+        return Ok(response.into_solution()?);
+    }
+}
+
+fn list_challenges(client: &Arc<ProcessClient>) -> anyhow::Result<Vec<ProcessChallenge>> {
+    let response: ListResponse =
+        match client.request(&ProcessRequest::list(), &CancellationToken::new()) {
+            Ok(r) => r,
+            Err(e)
+                if e.downcast_ref::<ChallengeError>()
+                    .is_some_and(|e| e.kind() == ChallengeErrorKind::TimedOut) =>
+            {
+                return Err(anyhow!(
+                    "No listing after {}s",
+                    client.command().timeout_secs
+                ))
+            }
+            Err(e) => return Err(e),
+        };
+    if response.version != PROTOCOL_VERSION {
+        return Err(anyhow!(
+            "Unsupported protocol version {}, expected {}",
+            response.version,
+            PROTOCOL_VERSION
+        ));
+    }
+    if let Some(error) = response.error {
+        return Err(anyhow!(error));
+    }
+    let challenges: Vec<ProcessChallenge> = response
+        .challenges
+        .into_iter()
+        .map(|c| ProcessChallenge {
+            id: c.id,
+            title: c.title,
+            description: c.description,
+            tags: c.tags,
+            client: client.clone(),
+        })
+        .collect();
+    check_challenge_ids(&challenges)?;
+    return Ok(challenges);
+}
+
+/// Challenges of a command group, kept once listed
+#[derive(Default)]
+struct Listing {
+    challenges: Option<Vec<ProcessChallenge>>,
+    failed_at: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl Listing {
+    fn update(&mut self, result: anyhow::Result<Vec<ProcessChallenge>>) {
+        match result {
+            Ok(challenges) => {
+                self.challenges = Some(challenges);
+                self.failed_at = None;
+                self.last_error = None;
+            }
+            Err(e) => {
+                self.failed_at = Some(Instant::now());
+                self.last_error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+/// Group whose challenges are listed and solved by a local executable, started on the first
+/// listing and kept running between requests
+pub struct ProcessGroupConfig {
+    client: Arc<ProcessClient>,
+    listing: Mutex<Listing>,
+}
+
+impl ProcessGroupConfig {
+    pub fn new(command: CommandGroupConfig) -> Self
+    where
+        Self: Sized,
+    {
+        return ProcessGroupConfig {
+            client: Arc::new(ProcessClient::new(command)),
+            listing: Mutex::new(Listing::default()),
+        };
+    }
+
+    /// Locks the listing, listing the challenges if they weren't listed yet
+    fn listing(&self) -> MutexGuard<'_, Listing> {
+        let mut listing = self.listing.lock().unwrap();
+        let should_list = listing.challenges.is_none()
+            && listing.failed_at.is_none_or(|t| t.elapsed() >= RETRY_DELAY);
+        if should_list {
+            listing.update(list_challenges(&self.client));
+        }
+        return listing;
+    }
+}
+
+impl GroupConfig for ProcessGroupConfig {
+    fn name(&self) -> &str {
+        return self.client.command().name.as_str();
+    }
+    fn id(&self) -> String {
+        return self.client.command().id();
+    }
+    fn url(&self) -> &str {
+        return self.client.command().url.as_str();
+    }
+
+    fn challenges(&self) -> Result<Vec<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        let listing = self.listing();
+        return match &listing.challenges {
+            Some(challenges) => Ok(challenges
+                .iter()
+                .map(|e| Box::new(e.clone()) as Box<dyn ChallengeConfig>)
+                .collect()),
+            None => Err(GroupUnavailableError::new(
+                self.name(),
+                listing.last_error.as_deref().unwrap_or_default(),
+            )),
+        };
+    }
+
+    /// Down until the challenges are listed, then degraded while the process keeps failing or
+    /// after a failed refresh
+    fn health(&self) -> GroupHealth {
+        let listing = self.listing();
+        if listing.challenges.is_none() {
+            return GroupHealth {
+                status: HealthStatus::Down,
+                last_error: listing.last_error.clone(),
+            };
+        }
+        return match self
+            .client
+            .last_error()
+            .or_else(|| listing.last_error.clone())
+        {
+            Some(e) => GroupHealth {
+                status: HealthStatus::Degraded,
+                last_error: Some(e),
+            },
+            None => GroupHealth::up(),
+        };
+    }
+
+    /// Restarts the process, e.g. after its executable was rebuilt, and lists the challenges
+    /// again. A pending solve fails rather than delaying the restart, the previous listing is
+    /// kept if the new one fails
+    fn refresh(&self) {
+        self.client.stop();
+        let result = list_challenges(&self.client);
+        self.listing.lock().unwrap().update(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::stub_server::stub_command;
+
+    #[test]
+    fn list_and_solve() {
+        let group = ProcessGroupConfig::new(stub_command(5));
+        assert_eq!(group.health(), GroupHealth::up());
+        let challenges = group.challenges().unwrap();
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0].title(), "Day 1");
+        assert_eq!(challenges[0].description(), "First");
+        assert_eq!(challenges[0].tags(), vec!["Math"]);
+        let solution = challenges[0]
+            .solve_part("input", 1, &SolveContext::default())
+            .unwrap();
+        assert_eq!(solution, Solution::new().with_part("Part 1", 42));
+        let error = challenges[0]
+            .solve_part("input", 2, &SolveContext::default())
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Part 2 does not exist");
+    }
+
+    #[rstest(
+        input,
+        expected_error,
+        expected_status,
+        case("fail", "Invalid input", HealthStatus::Up),
+        case(
+            "crash",
+            "The process exited (exit status: 3): oops",
+            HealthStatus::Degraded
+        )
+    )]
+    fn failed_solve(input: &str, expected_error: &str, expected_status: HealthStatus) {
+        let group = ProcessGroupConfig::new(stub_command(5));
+        let challenge = group.challenge("Day 1").unwrap().unwrap();
+        let error = challenge
+            .solve(input, &SolveContext::default())
+            .unwrap_err();
+        assert_eq!(error.to_string(), expected_error);
+        assert_eq!(group.health().status, expected_status);
+        // The process is restarted by the next solve
+        assert!(challenge.solve("input", &SolveContext::default()).is_ok());
+        assert_eq!(group.health(), GroupHealth::up());
+    }
+
+    #[test]
+    fn unavailable() {
+        let group = ProcessGroupConfig::new(CommandGroupConfig {
+            command: "./missing-command".to_owned(),
+            ..stub_command(5)
+        });
+        let error = group.challenges().err().unwrap();
+        assert_eq!(error.group, "Script");
+        assert!(error
+            .message
+            .starts_with("Unable to start ./missing-command"));
+        let health = group.health();
+        assert_eq!(health.status, HealthStatus::Down);
+        assert_eq!(health.last_error, Some(error.message));
+    }
+
+    #[test]
+    fn refresh() {
+        let path = env::temp_dir().join(format!("challenges-listing-{}", std::process::id()));
+        let list = |title: &str| {
+            let listing = format!(
+                r#"{{"version":1,"challenges":[{{"id":"1","title":"{}"}}]}}"#,
+                title
+            );
+            fs::write(&path, listing).unwrap();
+        };
+        list("Day 1");
+        let group = ProcessGroupConfig::new(CommandGroupConfig {
+            args: vec![
+                "-c".to_owned(),
+                format!(
+                    "while read request; do cat '{}'; echo; done",
+                    path.display()
+                ),
+            ],
+            ..stub_command(1)
+        });
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 1"]);
+        list("Day 2");
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 1"]);
+        group.refresh();
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 2"]);
+
+        // A failed refresh keeps the previous challenges
+        fs::remove_file(&path).unwrap();
+        group.refresh();
+        assert_eq!(group.challenge_names().unwrap(), vec!["Day 2"]);
+        assert_eq!(group.health().status, HealthStatus::Degraded);
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::{Arc, Mutex};
+
+pub use challenges_types::progress::ProgressReport;
+
+/// Progress of a solve, written by the solver and read by whoever started it.
+/// Solvers should report at the same pace as they check for cancellation, not on every iteration
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    report: Arc<Mutex<ProgressReport>>,
+}
+
+impl Progress {
+    /// Starts a new phase, resetting the fraction complete
+    pub fn set_phase(&self, phase: &str) {
+        let mut report = self.report.lock().unwrap();
+        report.phase = Some(phase.to_owned());
+        report.fraction = None;
+    }
+
+    pub fn set_fraction(&self, fraction: f64) {
+        self.report.lock().unwrap().fraction = Some(fraction.clamp(0.0, 1.0));
+    }
+
+    /// Reports `done` steps out of `total`
+    pub fn set_steps(&self, done: usize, total: usize) {
+        if total > 0 {
+            self.set_fraction(done as f64 / total as f64);
+        }
+    }
+
+    pub fn report(&self) -> ProgressReport {
+        return self.report.lock().unwrap().clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report() {
+        let progress = Progress::default();
+        let clone = progress.clone();
+        clone.set_phase("Part 1");
+        clone.set_steps(1, 4);
+        assert_eq!(
+            progress.report(),
+            ProgressReport {
+                phase: Some("Part 1".to_owned()),
+                fraction: Some(0.25)
+            }
+        );
+        clone.set_phase("Part 2");
+        assert_eq!(progress.report().fraction, None);
+    }
+}
@@ -0,0 +1,142 @@
+use super::{challenge_config::ChallengeConfig, group_config::GroupConfig};
+
+/// Built-in group, registered by `register_group!` next to its type
+pub struct GroupRegistration {
+    pub name: &'static str,
+    pub id: &'static str,
+    pub create: fn() -> Box<dyn GroupConfig>,
+}
+
+/// Challenge of a built-in group, registered by `register_challenge!` in its own module
+pub struct ChallengeRegistration {
+    pub group_id: &'static str,
+    pub module: &'static str,
+    pub create: fn() -> Box<dyn ChallengeConfig>,
+}
+
+inventory::collect!(GroupRegistration);
+inventory::collect!(ChallengeRegistration);
+
+/// Implemented once for each id of a built-in group, two groups with the same id fail to compile
+/// with conflicting implementations
+pub trait UniqueGroupId<const ID_HASH: u64> {}
+
+/// Implemented by a built-in group once for each id of its challenges, two challenges of the
+/// group with the same id fail to compile with conflicting implementations
+pub trait UniqueChallengeId<const ID_HASH: u64> {}
+
+/// Type implementing `UniqueGroupId`
+pub struct BuiltinGroups;
+
+/// FNV-1a hash of an id, usable in constant expressions
+pub const fn id_hash(id: &str) -> u64 {
+    let id = id.as_bytes();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut i = 0;
+    while i < id.len() {
+        hash ^= id[i] as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    return hash;
+}
+
+/// Built-in groups, in the order of their names
+pub fn builtin_groups() -> Vec<&'static GroupRegistration> {
+    let mut groups: Vec<&'static GroupRegistration> =
+        inventory::iter::<GroupRegistration>.into_iter().collect();
+    groups.sort_by_key(|g| g.name);
+    return groups;
+}
+
+/// Challenges registered for the group, in the order of their modules
+pub fn registered_challenges(group_id: &str) -> Vec<Box<dyn ChallengeConfig>> {
+    let mut registrations: Vec<&ChallengeRegistration> = inventory::iter::<ChallengeRegistration>
+        .into_iter()
+        .filter(|c| c.group_id == group_id)
+        .collect();
+    registrations.sort_by_key(|c| c.module);
+    return registrations.iter().map(|c| (c.create)()).collect();
+}
+
+/// Registers a built-in group, which provides its name and id as `NAME` and `ID` constants and
+/// a `new` constructor: `register_group!(AdventOfCode2020)`. An id that isn't a slug or two
+/// groups with the same id fail to compile
+macro_rules! register_group {
+    ($group:ident) => {
+        inventory::submit! {
+            $crate::groups::registry::GroupRegistration {
+                name: $group::NAME,
+                id: $group::ID,
+                create: || Box::new($group::new()),
+            }
+        }
+
+        impl
+            $crate::groups::registry::UniqueGroupId<
+                { $crate::groups::registry::id_hash($group::ID) },
+            > for $crate::groups::registry::BuiltinGroups
+        {
+        }
+
+        const _: () = assert!(
+            $crate::utils::is_slug($group::ID),
+            concat!("The id of ", stringify!($group), " isn't a slug")
+        );
+    };
+}
+
+/// Registers a challenge of a built-in group from its module, the challenge providing its id as
+/// an `ID` constant: `register_challenge!(AdventOfCode2020, Day1)`. The challenges of a group
+/// are listed in the order of their modules. An id that isn't a slug or two challenges of the
+/// group with the same id fail to compile
+macro_rules! register_challenge {
+    ($group:ty, $challenge:ident) => {
+        inventory::submit! {
+            $crate::groups::registry::ChallengeRegistration {
+                group_id: <$group>::ID,
+                module: module_path!(),
+                create: || Box::new($challenge {}),
+            }
+        }
+
+        impl
+            $crate::groups::registry::UniqueChallengeId<
+                { $crate::groups::registry::id_hash($challenge::ID) },
+            > for $group
+        {
+        }
+
+        const _: () = assert!(
+            $crate::utils::is_slug($challenge::ID),
+            concat!("The id of ", stringify!($challenge), " isn't a slug")
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groups::advent_of_code_2020::config::AdventOfCode2020;
+
+    #[test]
+    fn registered() {
+        let groups = builtin_groups();
+        assert_eq!(
+            groups.iter().map(|g| g.id).collect::<Vec<_>>(),
+            vec![AdventOfCode2020::ID]
+        );
+        let challenges = registered_challenges(AdventOfCode2020::ID);
+        assert_eq!(challenges.len(), 25);
+        assert_eq!(challenges[0].id(), "day-1-report-repair");
+        assert_eq!(challenges[24].id(), "day-25-combo-breaker");
+        assert!(registered_challenges("unknown").is_empty());
+    }
+
+    #[test]
+    fn id_hashes() {
+        assert_eq!(id_hash(""), 0xcbf29ce484222325);
+        assert_eq!(id_hash("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(id_hash("day-1"), id_hash("day-10"));
+    }
+}
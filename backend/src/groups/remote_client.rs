@@ -0,0 +1,296 @@
+use std::{
+    fmt,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION},
+    Client, RequestBuilder, Response,
+};
+use thiserror::Error;
+use tokio::runtime::{self, Runtime};
+
+use crate::config::RemoteGroupConfig;
+
+const RUNTIME_THREADS: usize = 2;
+
+lazy_static! {
+    // The requests of all the remote groups are multiplexed on the same few threads
+    static ref RUNTIME: Runtime = runtime::Builder::new_multi_thread()
+        .worker_threads(RUNTIME_THREADS)
+        .thread_name("remote-groups")
+        .enable_all()
+        .build()
+        .expect("Unable to start the runtime of the remote groups");
+}
+
+/// Waits for a request of the remote groups from synchronous code. Only the HTTP requests are
+/// async, the `GroupConfig` calls of remote groups block their thread until the requests end, so
+/// this must not be called from an async task: the API runs its group calls on the blocking
+/// thread pool
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    return RUNTIME.block_on(future);
+}
+
+/// Runs the request of a remote group in the background
+pub fn spawn<F: Future<Output = ()> + Send + 'static>(future: F) {
+    RUNTIME.spawn(future);
+}
+
+/// Requests to the group are paused after too many failures
+#[derive(Debug, Clone, Error)]
+pub struct CircuitOpenError {
+    pub group: String,
+    pub remaining: Duration,
+}
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} keeps failing, requests are paused for {:.1}s",
+            self.group,
+            self.remaining.as_secs_f64()
+        )
+    }
+}
+
+/// Counts the consecutive failures of a service and stops requests for a cooldown once they
+/// reach the threshold. After the cooldown requests go through again, a single failure then
+/// pauses them again while a success resets the count
+struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: u32, cooldown: Duration) -> Self {
+        return CircuitBreaker {
+            threshold: threshold,
+            cooldown: cooldown,
+            failures: 0,
+            open_until: None,
+        };
+    }
+
+    /// Time left before requests go through again
+    fn remaining(&self) -> Option<Duration> {
+        return self
+            .open_until
+            .and_then(|t| t.checked_duration_since(Instant::now()))
+            .filter(|r| *r > Duration::from_secs(0));
+    }
+
+    fn record(&mut self, success: bool) {
+        if success {
+            self.failures = 0;
+            self.open_until = None;
+            return;
+        }
+        self.failures += 1;
+        if self.threshold > 0 && self.failures >= self.threshold {
+            self.open_until = Some(Instant::now() + self.cooldown);
+        }
+    }
+}
+
+/// HTTP client of a remote group, shared by its listings and solves
+pub struct RemoteClient {
+    remote: RemoteGroupConfig,
+    client: Result<Client, String>,
+    breaker: Mutex<CircuitBreaker>,
+}
+
+impl RemoteClient {
+    pub fn new(remote: RemoteGroupConfig) -> Self {
+        let client = build_client(&remote).map_err(|e| e.to_string());
+        let breaker = CircuitBreaker::new(
+            remote.breaker_threshold,
+            Duration::from_secs(remote.breaker_cooldown_secs),
+        );
+        return RemoteClient {
+            remote: remote,
+            client: client,
+            breaker: Mutex::new(breaker),
+        };
+    }
+
+    pub fn remote(&self) -> &RemoteGroupConfig {
+        return &self.remote;
+    }
+
+    /// Sends a single request, failing right away with a `CircuitOpenError` while requests are
+    /// paused. Connection errors, timeouts and 5xx responses count as failures of the service
+    pub async fn send<F: FnOnce(&Client) -> RequestBuilder>(&self, request: F) -> Result<Response> {
+        let client = match &self.client {
+            Ok(c) => c,
+            Err(e) => return Err(anyhow!("Unable to create the client: {}", e)),
+        };
+        let remaining = self.breaker.lock().unwrap().remaining();
+        if let Some(remaining) = remaining {
+            return Err(CircuitOpenError {
+                group: self.remote.name.clone(),
+                remaining: remaining,
+            }
+            .into());
+        }
+        let result = request(client).send().await;
+        let success = match &result {
+            Ok(r) => !r.status().is_server_error(),
+            Err(_) => false,
+        };
+        self.breaker.lock().unwrap().record(success);
+        return Ok(result?);
+    }
+
+    /// Gets the path under the base URL, retrying failures with an exponential backoff.
+    /// Only meant for idempotent requests
+    pub async fn get(&self, path: &str) -> Result<Response> {
+        let url = self.remote.endpoint(path);
+        let mut backoff = Duration::from_millis(self.remote.retry_backoff_ms);
+        let mut retries = 0;
+        loop {
+            let result = self.send(|c| c.get(&url)).await;
+            let failed = match &result {
+                Ok(r) => r.status().is_server_error(),
+                Err(e) => !e.is::<CircuitOpenError>(),
+            };
+            if !failed || retries >= self.remote.list_retries {
+                return result;
+            }
+            retries += 1;
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
+
+/// Client sending the credentials of the group, trusting its CA bundle and applying its timeouts
+fn build_client(remote: &RemoteGroupConfig) -> Result<Client> {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = &remote.bearer_token {
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token))?,
+        );
+    }
+    if let Some((name, value)) = remote.auth_header() {
+        headers.insert(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(value)?,
+        );
+    }
+    let mut builder = Client::builder()
+        .connect_timeout(Duration::from_secs(remote.connect_timeout_secs))
+        .timeout(Duration::from_secs(remote.timeout_secs))
+        .default_headers(headers);
+    if let Some(certificate) = remote.certificate()? {
+        builder = builder.add_root_certificate(certificate);
+    }
+    return Ok(builder.build()?);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::stub_server::{closed_server, hung_server, stub_server, LIST};
+
+    fn remote(base_url: String) -> RemoteGroupConfig {
+        return RemoteGroupConfig {
+            name: "Stub".to_owned(),
+            base_url: base_url,
+            timeout_secs: 1,
+            retry_backoff_ms: 1,
+            ..RemoteGroupConfig::default()
+        };
+    }
+
+    #[test]
+    fn circuit_breaker() {
+        let mut breaker = CircuitBreaker::new(2, Duration::from_millis(50));
+        breaker.record(false);
+        assert!(breaker.remaining().is_none());
+        breaker.record(false);
+        assert!(breaker.remaining().is_some());
+        thread::sleep(Duration::from_millis(60));
+        assert!(breaker.remaining().is_none());
+        // A single failure after the cooldown is enough to pause again
+        breaker.record(false);
+        assert!(breaker.remaining().is_some());
+        thread::sleep(Duration::from_millis(60));
+        breaker.record(true);
+        breaker.record(false);
+        assert!(breaker.remaining().is_none());
+
+        let mut disabled = CircuitBreaker::new(0, Duration::from_secs(60));
+        (0..10).for_each(|_| disabled.record(false));
+        assert!(disabled.remaining().is_none());
+    }
+
+    #[rstest(
+        list_retries,
+        expected_status,
+        expected_requests,
+        case(0, 503, 1),
+        case(1, 500, 2),
+        case(2, 200, 3),
+        case(5, 200, 3)
+    )]
+    fn get_retries(list_retries: u32, expected_status: u16, expected_requests: usize) {
+        let (base_url, requests) =
+            stub_server(vec![(503, "text/plain", ""), (500, "text/plain", ""), LIST]);
+        let client = RemoteClient::new(RemoteGroupConfig {
+            list_retries: list_retries,
+            ..remote(base_url)
+        });
+        let response = block_on(client.get("list/")).unwrap();
+        assert_eq!(response.status().as_u16(), expected_status);
+        assert_eq!(requests.try_iter().count(), expected_requests);
+    }
+
+    #[test]
+    fn fails_fast_when_open() {
+        let client = RemoteClient::new(RemoteGroupConfig {
+            list_retries: 5,
+            breaker_threshold: 3,
+            ..remote(closed_server())
+        });
+        // The retries stop as soon as requests are paused
+        let error = block_on(client.get("list/")).unwrap_err();
+        assert!(error.is::<CircuitOpenError>());
+        let start = Instant::now();
+        let error = block_on(client.send(|c| c.post(client.remote().endpoint("solve/1"))));
+        assert!(error.unwrap_err().is::<CircuitOpenError>());
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn read_timeout() {
+        let client = RemoteClient::new(RemoteGroupConfig {
+            list_retries: 0,
+            ..remote(hung_server())
+        });
+        let start = Instant::now();
+        let error = block_on(client.get("list/")).unwrap_err();
+        assert!(error.downcast_ref::<reqwest::Error>().unwrap().is_timeout());
+        assert!(start.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn invalid_ca_bundle() {
+        let client = RemoteClient::new(RemoteGroupConfig {
+            ca_bundle: Some("missing.pem".into()),
+            ..remote("https://localhost".to_owned())
+        });
+        assert!(block_on(client.get("list/")).is_err());
+    }
+}
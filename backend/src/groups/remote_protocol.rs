@@ -0,0 +1,236 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    challenge_config::{ChallengeError, ChallengeErrorKind},
+    solution::{Part, Solution},
+};
+
+/// Version of the JSON protocol spoken with remote groups, also the prefix of its endpoints
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Challenge as listed by `GET /list/`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ListedChallenge {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Body of `POST /v1/solve/<id>`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SolveRequest {
+    pub version: u32,
+    pub input: String,
+    /// Time left before the solve is abandoned, the service may stop early and report `timed_out`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl SolveRequest {
+    pub fn new(input: &str, timeout_ms: Option<u64>) -> Self {
+        return SolveRequest {
+            version: PROTOCOL_VERSION,
+            input: input.to_owned(),
+            timeout_ms: timeout_ms,
+        };
+    }
+}
+
+/// Line written to the standard input of process groups, answered by a single line on their
+/// standard output
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProcessRequest {
+    /// Answered with a `ListResponse`
+    List { version: u32 },
+    /// Answered with a `SolveResponse`
+    Solve {
+        id: String,
+        #[serde(flatten)]
+        request: SolveRequest,
+    },
+}
+
+impl ProcessRequest {
+    pub fn list() -> Self {
+        return ProcessRequest::List {
+            version: PROTOCOL_VERSION,
+        };
+    }
+
+    pub fn solve(id: &str, input: &str, timeout_ms: Option<u64>) -> Self {
+        return ProcessRequest::Solve {
+            id: id.to_owned(),
+            request: SolveRequest::new(input, timeout_ms),
+        };
+    }
+}
+
+/// Answer of process groups to `{"type": "list"}`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ListResponse {
+    pub version: u32,
+    #[serde(default)]
+    pub challenges: Vec<ListedChallenge>,
+    /// Why the challenges can't be listed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolveStatus {
+    Ok,
+    Error,
+    TimedOut,
+    Cancelled,
+}
+
+/// Body of the responses to `POST /v1/solve/<id>`, whatever their HTTP status
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SolveResponse {
+    pub version: u32,
+    pub status: SolveStatus,
+    /// Answers of every part, in order, when the status is `ok`
+    #[serde(default)]
+    pub parts: Vec<Part>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Time spent solving on the service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<f64>,
+}
+
+impl SolveResponse {
+    /// Response reporting the result of a solve that took `duration`
+    pub fn from_result(result: &anyhow::Result<Solution>, duration: Duration) -> Self {
+        let (status, parts, error) = match result {
+            Ok(solution) => (SolveStatus::Ok, solution.parts().clone(), None),
+            Err(e) => {
+                let status = match e.downcast_ref::<ChallengeError>().map(|e| e.kind()) {
+                    Some(ChallengeErrorKind::TimedOut) => SolveStatus::TimedOut,
+                    Some(ChallengeErrorKind::Cancelled) => SolveStatus::Cancelled,
+                    _ => SolveStatus::Error,
+                };
+                (status, vec![], Some(e.to_string()))
+            }
+        };
+        return SolveResponse {
+            version: PROTOCOL_VERSION,
+            status: status,
+            parts: parts,
+            error: error,
+            duration_ms: Some(duration.as_micros() as f64 / 1000.0),
+        };
+    }
+
+    /// Solution of a successful solve, the failures are mapped to their `ChallengeError`
+    pub fn into_solution(self) -> Result<Solution, ChallengeError> {
+        if self.version != PROTOCOL_VERSION {
+            return Err(ChallengeError::new(&format!(
+                "Unsupported protocol version {}, expected {}",
+                self.version, PROTOCOL_VERSION
+            )));
+        }
+        return match self.status {
+            SolveStatus::Ok => Ok(Solution::from_parts(self.parts)),
+            SolveStatus::Error => Err(ChallengeError::new(
+                self.error.as_deref().unwrap_or("The remote solve failed"),
+            )),
+            SolveStatus::TimedOut => Err(ChallengeError::timed_out()),
+            SolveStatus::Cancelled => Err(ChallengeError::cancelled()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        request,
+        expected,
+        case(ProcessRequest::list(), r#"{"type":"list","version":1}"#),
+        case(
+            ProcessRequest::solve("1", "input", None),
+            r#"{"type":"solve","id":"1","version":1,"input":"input"}"#
+        ),
+        case(
+            ProcessRequest::solve("1", "input", Some(500)),
+            r#"{"type":"solve","id":"1","version":1,"input":"input","timeout_ms":500}"#
+        )
+    )]
+    fn process_request(request: ProcessRequest, expected: &str) {
+        assert_eq!(serde_json::to_string(&request).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_str::<ProcessRequest>(expected).unwrap(),
+            request
+        );
+    }
+
+    #[rstest(
+        response,
+        expected,
+        case(
+            r#"{"version": 1, "status": "ok", "parts": [{"name": "Part 1", "value": 42}], "duration_ms": 1.5}"#,
+            Ok(Solution::new().with_part("Part 1", 42))
+        ),
+        case(
+            r#"{"version": 1, "status": "error", "error": "Invalid input"}"#,
+            Err((ChallengeErrorKind::Failed, "Invalid input"))
+        ),
+        case(
+            r#"{"version": 1, "status": "error"}"#,
+            Err((ChallengeErrorKind::Failed, "The remote solve failed"))
+        ),
+        case(
+            r#"{"version": 1, "status": "timed_out"}"#,
+            Err((ChallengeErrorKind::TimedOut, "The solve timed out"))
+        ),
+        case(
+            r#"{"version": 1, "status": "cancelled"}"#,
+            Err((ChallengeErrorKind::Cancelled, "The solve was cancelled"))
+        ),
+        case(
+            r#"{"version": 2, "status": "ok"}"#,
+            Err((ChallengeErrorKind::Failed, "Unsupported protocol version 2, expected 1"))
+        )
+    )]
+    fn into_solution(response: &str, expected: Result<Solution, (ChallengeErrorKind, &str)>) {
+        let response: SolveResponse = serde_json::from_str(response).unwrap();
+        let result = response
+            .into_solution()
+            .map_err(|e| (e.kind(), e.to_string()));
+        assert_eq!(
+            result,
+            expected.map_err(|(kind, message)| (kind, message.to_owned()))
+        );
+    }
+
+    #[rstest(
+        result,
+        case(Ok(Solution::new().with_part("Part 1", 42))),
+        case(Err(ChallengeError::new("Invalid input").into())),
+        case(Err(ChallengeError::timed_out().into())),
+        case(Err(ChallengeError::cancelled().into()))
+    )]
+    fn from_result(result: anyhow::Result<Solution>) {
+        let response = SolveResponse::from_result(&result, Duration::from_millis(5));
+        assert_eq!(response.duration_ms, Some(5.0));
+        let solution = response
+            .into_solution()
+            .map_err(|e| (e.kind(), e.to_string()));
+        let expected = result.map_err(|e| {
+            let e = e.downcast::<ChallengeError>().unwrap();
+            (e.kind(), e.to_string())
+        });
+        assert_eq!(solution, expected);
+    }
+}
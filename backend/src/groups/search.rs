@@ -0,0 +1,153 @@
+use std::cmp::Reverse;
+
+pub use challenges_types::groups::SearchResult;
+
+use super::{challenge_config::ChallengeConfig, group_config::GroupConfig};
+
+/// Scores of a query term found in the title, in a tag, or in the description of a challenge
+const TITLE_SCORE: u32 = 6;
+const TAG_SCORE: u32 = 4;
+const PARTIAL_TAG_SCORE: u32 = 2;
+const DESCRIPTION_SCORE: u32 = 1;
+
+struct IndexEntry {
+    group: String,
+    group_id: String,
+    challenge: String,
+    challenge_id: String,
+    tags: Vec<String>,
+    // Lowercase copies matched by the queries
+    title: String,
+    description: String,
+    lowercase_tags: Vec<String>,
+}
+
+impl IndexEntry {
+    /// Score of a lowercase term, 0 if the challenge doesn't contain it
+    fn score(&self, term: &str) -> u32 {
+        if self.title.contains(term) {
+            return TITLE_SCORE;
+        }
+        if self.lowercase_tags.iter().any(|t| t == term) {
+            return TAG_SCORE;
+        }
+        if self.lowercase_tags.iter().any(|t| t.contains(term)) {
+            return PARTIAL_TAG_SCORE;
+        }
+        if self.description.contains(term) {
+            return DESCRIPTION_SCORE;
+        }
+        return 0;
+    }
+}
+
+/// Titles, descriptions and tags of the challenges, searched by case-insensitive terms
+#[derive(Default)]
+pub struct SearchIndex {
+    entries: Vec<IndexEntry>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        return SearchIndex::default();
+    }
+
+    pub fn add(&mut self, group: &dyn GroupConfig, challenge: &dyn ChallengeConfig) {
+        let tags: Vec<String> = challenge.tags().iter().map(|t| (*t).to_owned()).collect();
+        self.entries.push(IndexEntry {
+            group: group.name().to_owned(),
+            group_id: group.id(),
+            challenge: challenge.title().to_owned(),
+            challenge_id: challenge.id(),
+            title: challenge.title().to_lowercase(),
+            description: challenge.description().to_lowercase(),
+            lowercase_tags: tags.iter().map(|t| t.to_lowercase()).collect(),
+            tags: tags,
+        });
+    }
+
+    /// Challenges containing every term of the query, best scores first then in the order they
+    /// were added. An empty query matches nothing
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        let query = query.to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        if terms.is_empty() {
+            return vec![];
+        }
+        let mut results: Vec<SearchResult> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let scores: Vec<u32> = terms.iter().map(|t| entry.score(t)).collect();
+                if scores.contains(&0) {
+                    return None;
+                }
+                return Some(SearchResult {
+                    group_key: entry.group_id.clone(),
+                    group: entry.group.clone(),
+                    challenge_key: entry.challenge_id.clone(),
+                    challenge: entry.challenge.clone(),
+                    tags: entry.tags.clone(),
+                    score: scores.iter().sum(),
+                });
+            })
+            .collect();
+        results.sort_by_key(|r| Reverse(r.score));
+        return results;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::{advent_of_code_2020::config::AdventOfCode2020, group_config::GroupConfig};
+
+    fn index() -> SearchIndex {
+        let group = AdventOfCode2020::new();
+        let mut index = SearchIndex::new();
+        for challenge in group.challenges().unwrap() {
+            index.add(&group, challenge.as_ref());
+        }
+        return index;
+    }
+
+    #[rstest(
+        query,
+        expected,
+        case("", vec![]),
+        case("   ", vec![]),
+        case("Report", vec!["Day 1: Report Repair"]),
+        case("NUMBER theory", vec!["Day 13: Shuttle Search", "Day 25: Combo Breaker"]),
+        case("hex", vec!["Day 24: Lobby Layout"]),
+        case("cellular grid", vec!["Day 11: Seating System", "Day 17: Conway Cubes", "Day 24: Lobby Layout"]),
+        case("search", vec!["Day 13: Shuttle Search", "Day 1: Report Repair", "Day 9: Encoding Error", "Day 20: Jurassic Jigsaw"]),
+        case("graph unknown", vec![])
+    )]
+    fn search(query: &str, expected: Vec<&str>) {
+        let results = index().search(query);
+        let challenges: Vec<&str> = results.iter().map(|r| r.challenge.as_str()).collect();
+        assert_eq!(challenges, expected);
+    }
+
+    #[test]
+    fn result() {
+        let results = index().search("recursion graph");
+        assert_eq!(
+            results,
+            vec![SearchResult {
+                group_key: "advent-of-code-2020".to_owned(),
+                group: AdventOfCode2020::NAME.to_owned(),
+                challenge_key: "day-7-handy-haversacks".to_owned(),
+                challenge: "Day 7: Handy Haversacks".to_owned(),
+                tags: vec![
+                    "graph".to_owned(),
+                    "recursion".to_owned(),
+                    "parsing".to_owned()
+                ],
+                score: 2 * TAG_SCORE,
+            }]
+        );
+    }
+}
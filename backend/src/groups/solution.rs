@@ -0,0 +1 @@
+pub use challenges_types::solution::{Part, PartValue, Solution};
@@ -0,0 +1,35 @@
+use anyhow::Result;
+
+use super::{cancellation::CancellationToken, progress::Progress};
+
+/// What a solver gets besides its input: the token telling it when to stop and the progress it
+/// reports to whoever started the solve. Clones share both
+#[derive(Clone, Debug, Default)]
+pub struct SolveContext {
+    token: CancellationToken,
+    progress: Progress,
+}
+
+impl SolveContext {
+    /// Context of a solve stopped through the token, reporting to a progress of its own
+    pub fn new(token: CancellationToken) -> Self {
+        return SolveContext {
+            token: token,
+            progress: Progress::default(),
+        };
+    }
+
+    pub fn token(&self) -> &CancellationToken {
+        return &self.token;
+    }
+
+    /// Handle through which the solver reports its progress
+    pub fn progress(&self) -> &Progress {
+        return &self.progress;
+    }
+
+    /// Fails once the solve should stop, see `CancellationToken::check`
+    pub fn check(&self) -> Result<()> {
+        return self.token.check();
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use crate::config::CommandGroupConfig;
+
+/// Status, content type and body of a stub response
+pub type StubResponse = (u16, &'static str, &'static str);
+
+pub const LIST: StubResponse = (
+    200,
+    "application/json",
+    r#"[{"id": "1", "title": "Day 1", "description": ""}]"#,
+);
+
+pub struct StubRequest {
+    /// Request line followed by the headers
+    pub head: Vec<String>,
+    pub body: String,
+}
+
+impl StubRequest {
+    pub fn has_header(&self, header: &str) -> bool {
+        return self.head.iter().any(|h| h.eq_ignore_ascii_case(header));
+    }
+}
+
+/// Serves the responses in order on a local port, and sends back each request received.
+/// The server stops listening after the last response, so later requests are refused
+pub fn stub_server(responses: Vec<StubResponse>) -> (String, Receiver<StubRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for (status, content_type, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = vec![];
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_owned();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(l) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = l.trim().parse().unwrap();
+                }
+                head.push(line);
+            }
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            let _ = sender.send(StubRequest {
+                head: head,
+                body: String::from_utf8(request_body).unwrap(),
+            });
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {} Stub\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                content_type,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    return (format!("http://{}", address), receiver);
+}
+
+/// Accepts connections without ever answering them
+pub fn hung_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let mut streams = vec![];
+        for stream in listener.incoming() {
+            streams.push(stream);
+        }
+    });
+    return format!("http://{}", address);
+}
+
+/// URL on which nothing listens
+pub fn closed_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    return format!("http://{}", listener.local_addr().unwrap());
+}
+
+/// Answers the listings of command groups, and their solves according to the input
+const STUB_SCRIPT: &str = r#"
+    while read -r line; do
+        case "$line" in
+            *'"type":"list"'*)
+                echo '{"version":1,"challenges":[{"id":"1","title":"Day 1","description":"First","tags":["Math"]}]}' ;;
+            *'"input":"fail"'*) echo '{"version":1,"status":"error","error":"Invalid input"}' ;;
+            *'"input":"hang"'*) sleep 5 ;;
+            *'"input":"crash"'*) echo "oops" >&2; exit 3 ;;
+            *'"input":"garbage"'*) echo "garbage" ;;
+            *) echo '{"version":1,"status":"ok","parts":[{"name":"Part 1","value":42}]}' ;;
+        esac
+    done
+"#;
+
+/// Command group running the stub script
+pub fn stub_command(timeout_secs: u64) -> CommandGroupConfig {
+    return CommandGroupConfig {
+        name: "Script".to_owned(),
+        command: "sh".to_owned(),
+        args: vec!["-c".to_owned(), STUB_SCRIPT.to_owned()],
+        timeout_secs: timeout_secs,
+        ..CommandGroupConfig::default()
+    };
+}
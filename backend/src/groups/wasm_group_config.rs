@@ -0,0 +1,394 @@
+use std::{fs, sync::Arc};
+
+use anyhow::{anyhow, bail, Result};
+use serde::de::DeserializeOwned;
+use wasmi::{
+    core::TrapCode,
+    errors::{ErrorKind, MemoryError},
+    Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder,
+};
+
+use super::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    group_config::{check_challenge_ids, GroupConfig, GroupUnavailableError},
+    health::{GroupHealth, HealthStatus},
+    remote_protocol::{ListResponse, ProcessRequest, SolveResponse, PROTOCOL_VERSION},
+    solution::Solution,
+    solve_context::SolveContext,
+};
+use crate::config::WasmModuleConfig;
+
+/// State of the store of a request
+struct Sandbox {
+    limits: StoreLimits,
+}
+
+/// Compiled WebAssembly module. It exports its `memory` along with:
+///
+/// - `alloc(len: i32) -> i32`: address where the host writes a request of `len` bytes
+/// - `handle(ptr: i32, len: i32) -> i64`: answers the request, returning the address of the
+///   answer in the high 32 bits and its length in the low 32 bits
+///
+/// Requests and answers are the JSON messages of command groups (`ProcessRequest`). The module
+/// can't import anything, so it has no access to the host
+struct WasmModule {
+    config: WasmModuleConfig,
+    engine: Engine,
+    module: Module,
+}
+
+impl WasmModule {
+    fn load(config: WasmModuleConfig) -> Result<WasmModule> {
+        return match fs::read(&config.path) {
+            Ok(wasm) => WasmModule::new(config, &wasm),
+            Err(e) => bail!("Unable to read {}: {}", config.path.display(), e),
+        };
+    }
+
+    fn new(config: WasmModuleConfig, wasm: &[u8]) -> Result<WasmModule> {
+        let mut engine_config = wasmi::Config::default();
+        engine_config.consume_fuel(true);
+        let engine = Engine::new(&engine_config);
+        let module = Module::new(&engine, wasm)?;
+        return Ok(WasmModule {
+            config: config,
+            engine: engine,
+            module: module,
+        });
+    }
+
+    /// Answers the request in a new instance of the module, which can't use more than the fuel
+    /// and memory limit of the group
+    fn request<R: DeserializeOwned>(&self, request: &ProcessRequest) -> Result<R> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size((self.config.memory_limit_mb * 1024 * 1024) as usize)
+            .trap_on_grow_failure(true)
+            .build();
+        let mut store = Store::new(&self.engine, Sandbox { limits: limits });
+        store.limiter(|s| &mut s.limits);
+        store
+            .set_fuel(self.config.fuel)
+            .map_err(|e| anyhow!("{}", e))?;
+
+        let linker = Linker::<Sandbox>::new(&self.engine);
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .and_then(|i| i.start(&mut store))
+            .map_err(|e| self.error(e))?;
+        let memory = match instance.get_memory(&store, "memory") {
+            Some(m) => m,
+            None => bail!("The module does not export its memory"),
+        };
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc")?;
+        let handle = instance.get_typed_func::<(i32, i32), i64>(&store, "handle")?;
+
+        let request = serde_json::to_vec(request)?;
+        let len = request.len() as i32;
+        let ptr = alloc.call(&mut store, len).map_err(|e| self.error(e))?;
+        memory
+            .write(&mut store, ptr as u32 as usize, &request)
+            .map_err(|e| anyhow!("Unable to write the request at {}: {}", ptr, e))?;
+        let answer = handle
+            .call(&mut store, (ptr, len))
+            .map_err(|e| self.error(e))? as u64;
+        let mut buffer = vec![0; (answer & 0xffff_ffff) as usize];
+        memory
+            .read(&store, (answer >> 32) as usize, &mut buffer)
+            .map_err(|e| anyhow!("Unable to read the answer: {}", e))?;
+        return serde_json::from_slice(&buffer).map_err(|e| {
+            anyhow!(
+                "Invalid answer \"{}\": {}",
+                String::from_utf8_lossy(&buffer),
+                e
+            )
+        });
+    }
+
+    /// Maps the exhaustion of the fuel or memory to a `ChallengeError`
+    fn error(&self, error: wasmi::Error) -> anyhow::Error {
+        let memory_exceeded = matches!(
+            error.kind(),
+            ErrorKind::TrapCode(TrapCode::GrowthOperationLimited)
+                | ErrorKind::Memory(MemoryError::OutOfBoundsGrowth)
+        );
+        if memory_exceeded {
+            let message = format!(
+                "The module needed more than {} MB of memory",
+                self.config.memory_limit_mb
+            );
+            return ChallengeError::limit_exceeded(&message).into();
+        }
+        if error.as_trap_code() == Some(TrapCode::OutOfFuel) {
+            let message = format!("The module ran out of fuel ({})", self.config.fuel);
+            return ChallengeError::limit_exceeded(&message).into();
+        }
+        return anyhow!("The module failed: {}", error);
+    }
+}
+
+#[derive(Clone)]
+pub struct WasmChallenge {
+    id: String,
+    title: String,
+    description: String,
+    tags: Vec<String>,
+    module: Arc<WasmModule>,
+}
+
+impl ChallengeConfig for WasmChallenge {
+    fn title(&self) -> &str {
+        return self.title.as_str();
+    }
+
+    fn id(&self) -> String {
+        return self.id.clone();
+    }
+
+    fn description(&self) -> &str {
+        return self.description.as_str();
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        return self.tags.iter().map(String::as_str).collect();
+    }
+
+    fn solve_part(
+        &self,
+        input: &str,
+        part: usize,
+        context: &SolveContext,
+    ) -> anyhow::Result<Solution> {
+        // Modules always solve all parts, only keep the requested one
+        let solution = self.solve(input, context)?;
+        return match solution.nth_part(part) {
+            Some(s) => Ok(s),
+            None => Err(ChallengeError::invalid_part(part).into()),
+        };
+    }
+
+    fn solves_parts_together(&self) -> bool {
+        return true;
+    }
+
+    /// The module can't be interrupted, its fuel bounds how long it runs
+    fn solve(&self, input: &str, context: &SolveContext) -> anyhow::Result<Solution> {
+        let token = context.token();
+        token.check()?;
+        let timeout_ms = token.remaining().map(|r| r.as_millis() as u64);
+        let request = ProcessRequest::solve(&self.id, input, timeout_ms);
+        let response: SolveResponse = self.module.request(&request)?;
+        return Ok(response.into_solution()?);
+    }
+}
+
+fn list_challenges(module: &Arc<WasmModule>) -> Result<Vec<WasmChallenge>> {
+    let response: ListResponse = module.request(&ProcessRequest::list())?;
+    if response.version != PROTOCOL_VERSION {
+        bail!(
+            "Unsupported protocol version {}, expected {}",
+            response.version,
+            PROTOCOL_VERSION
+        );
+    }
+    if let Some(error) = response.error {
+        bail!(error);
+    }
+    let challenges: Vec<WasmChallenge> = response
+        .challenges
+        .into_iter()
+        .map(|c| WasmChallenge {
+            id: c.id,
+            title: c.title,
+            description: c.description,
+            tags: c.tags,
+            module: module.clone(),
+        })
+        .collect();
+    check_challenge_ids(&challenges)?;
+    return Ok(challenges);
+}
+
+/// Group of a WebAssembly module, compiled and listed once loaded. Each request runs in a new
+/// instance of the module, so untrusted solutions can't reach the host nor keep any state
+pub struct WasmGroupConfig {
+    config: WasmModuleConfig,
+    challenges: Result<Vec<WasmChallenge>, String>,
+}
+
+impl WasmGroupConfig {
+    pub fn new(config: WasmModuleConfig) -> Self {
+        let module = WasmModule::load(config.clone());
+        return WasmGroupConfig::from_module(config, module);
+    }
+
+    fn from_module(config: WasmModuleConfig, module: Result<WasmModule>) -> Self {
+        let challenges = module
+            .and_then(|m| list_challenges(&Arc::new(m)))
+            .map_err(|e| e.to_string());
+        return WasmGroupConfig {
+            config: config,
+            challenges: challenges,
+        };
+    }
+}
+
+impl GroupConfig for WasmGroupConfig {
+    fn name(&self) -> &str {
+        return self.config.name.as_str();
+    }
+    fn id(&self) -> String {
+        return self.config.id();
+    }
+    fn url(&self) -> &str {
+        return self.config.url.as_str();
+    }
+
+    fn challenges(&self) -> Result<Vec<Box<dyn ChallengeConfig>>, GroupUnavailableError> {
+        return match &self.challenges {
+            Ok(challenges) => Ok(challenges
+                .iter()
+                .map(|e| Box::new(e.clone()) as Box<dyn ChallengeConfig>)
+                .collect()),
+            Err(e) => Err(GroupUnavailableError::new(self.name(), e)),
+        };
+    }
+
+    fn health(&self) -> GroupHealth {
+        return match &self.challenges {
+            Ok(_) => GroupHealth::up(),
+            Err(e) => GroupHealth {
+                status: HealthStatus::Down,
+                last_error: Some(e.clone()),
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::groups::challenge_config::ChallengeErrorKind;
+
+    const LIST: &str = r#"{"version":1,"challenges":[{"id":"1","title":"Day 1","description":""},{"id":"loop","title":"Loop","description":""},{"id":"grow","title":"Grow","description":""},{"id":"unreachable","title":"Unreachable","description":""}]}"#;
+    const SOLVED: &str = r#"{"version":1,"status":"ok","parts":[{"name":"Part 1","value":42}]}"#;
+
+    /// Module answering the listings, and the solves according to the first letter of the id
+    fn module_wat() -> String {
+        let data = |offset: usize, s: &str| {
+            format!(
+                "(data (i32.const {}) \"{}\")",
+                offset,
+                s.replace('"', "\\\"")
+            )
+        };
+        let answer = |offset: usize, s: &str| ((offset as u64) << 32) | s.len() as u64;
+        return format!(
+            r#"(module
+                (memory (export "memory") 1)
+                {list_data}
+                {solved_data}
+                (func (export "alloc") (param $len i32) (result i32)
+                    (local $missing i32)
+                    (local.set $missing (i32.sub
+                        (i32.add (i32.const 4096) (local.get $len))
+                        (i32.mul (memory.size) (i32.const 65536))))
+                    (if (i32.gt_s (local.get $missing) (i32.const 0))
+                        (then (drop (memory.grow
+                            (i32.add (i32.div_u (local.get $missing) (i32.const 65536)) (i32.const 1))))))
+                    (i32.const 4096))
+                (func (export "handle") (param $ptr i32) (param $len i32) (result i64)
+                    (local $id i32)
+                    ;; {{"type":"list" or {{"type":"solve","id":"<id>"
+                    (if (i32.eq (i32.load8_u offset=9 (local.get $ptr)) (i32.const 108))
+                        (then (return (i64.const {list}))))
+                    (local.set $id (i32.load8_u offset=22 (local.get $ptr)))
+                    (if (i32.eq (local.get $id) (i32.const 108))
+                        (then (loop $spin (br $spin))))
+                    (if (i32.eq (local.get $id) (i32.const 103))
+                        (then (loop $more (drop (memory.grow (i32.const 1))) (br $more))))
+                    (if (i32.eq (local.get $id) (i32.const 117))
+                        (then unreachable))
+                    (i64.const {solved})))"#,
+            list_data = data(0, LIST),
+            solved_data = data(1024, SOLVED),
+            list = answer(0, LIST),
+            solved = answer(1024, SOLVED),
+        );
+    }
+
+    fn group(wasm: &[u8], memory_limit_mb: u64) -> WasmGroupConfig {
+        let config = WasmModuleConfig {
+            name: "Wasm".to_owned(),
+            fuel: 1_000_000,
+            memory_limit_mb: memory_limit_mb,
+            ..WasmModuleConfig::default()
+        };
+        let module = WasmModule::new(config.clone(), wasm);
+        return WasmGroupConfig::from_module(config, module);
+    }
+
+    #[rstest(
+        title,
+        expected,
+        case("Day 1", Ok(Solution::new().with_part("Part 1", 42))),
+        case(
+            "Loop",
+            Err((ChallengeErrorKind::LimitExceeded, "The module ran out of fuel (1000000)"))
+        ),
+        case(
+            "Grow",
+            Err((ChallengeErrorKind::LimitExceeded, "The module needed more than 1 MB of memory"))
+        ),
+        case(
+            "Unreachable",
+            Err((ChallengeErrorKind::Failed, "The module failed: wasm `unreachable` instruction executed"))
+        )
+    )]
+    fn solve(title: &str, expected: Result<Solution, (ChallengeErrorKind, &str)>) {
+        let wasm = wat::parse_str(module_wat()).unwrap();
+        let group = group(&wasm, 1);
+        assert_eq!(group.health(), GroupHealth::up());
+        let challenge = group.challenge(title).unwrap().unwrap();
+        let result = challenge
+            .solve("input", &SolveContext::default())
+            .map_err(|e| match e.downcast::<ChallengeError>() {
+                Ok(e) => (e.kind(), e.to_string()),
+                Err(e) => (ChallengeErrorKind::Failed, e.to_string()),
+            });
+        assert_eq!(result, expected.map_err(|(k, m)| (k, m.to_owned())));
+    }
+
+    #[rstest(
+        wasm,
+        memory_limit_mb,
+        expected_error,
+        case(b"not wasm".to_vec(), 1, "magic header not detected"),
+        case(wat::parse_str("(module)").unwrap(), 1, "The module does not export its memory"),
+        case(
+            wat::parse_str(r#"(module (memory (export "memory") 17))"#).unwrap(),
+            1,
+            "The module needed more than 1 MB of memory"
+        )
+    )]
+    fn unavailable(wasm: Vec<u8>, memory_limit_mb: u64, expected_error: &str) {
+        let group = group(&wasm, memory_limit_mb);
+        let error = group.challenges().err().unwrap();
+        assert!(error.message.contains(expected_error), "{}", error);
+        assert_eq!(group.health().status, HealthStatus::Down);
+    }
+
+    #[test]
+    fn missing_module() {
+        let group = WasmGroupConfig::new(WasmModuleConfig {
+            name: "Wasm".to_owned(),
+            path: PathBuf::from("missing.wasm"),
+            ..WasmModuleConfig::default()
+        });
+        let error = group.challenges().err().unwrap();
+        assert!(error.message.starts_with("Unable to read missing.wasm"));
+    }
+}
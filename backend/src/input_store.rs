@@ -0,0 +1,163 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+
+use crate::{
+    groups::{challenge_config::ChallengeConfig, group_config::GroupConfig},
+    utils::create_key,
+};
+
+const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+/// Identifies the saved input and the answers of a challenge: they are named after the ids of
+/// the challenge and its group, and were named after the keys of their names in the first
+/// versions
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChallengeKey {
+    pub group_id: String,
+    pub group_name: String,
+    pub challenge_id: String,
+    pub challenge_title: String,
+}
+
+impl ChallengeKey {
+    pub fn new(group: &dyn GroupConfig, challenge: &dyn ChallengeConfig) -> Self {
+        return ChallengeKey {
+            group_id: group.id(),
+            group_name: group.name().to_owned(),
+            challenge_id: challenge.id(),
+            challenge_title: challenge.title().to_owned(),
+        };
+    }
+
+    /// Keys of the group and the challenge in the first versions
+    pub fn legacy_keys(&self) -> (String, String) {
+        return (
+            create_key(&self.group_name),
+            create_key(&self.challenge_title),
+        );
+    }
+}
+
+/// Puzzle inputs saved on disk, laid out as `<group id>/<challenge id>.txt`. Inputs saved by the
+/// first versions under the keys of the names are still read
+#[derive(Clone)]
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: &Path) -> Self {
+        return InputStore {
+            root: root.to_path_buf(),
+        };
+    }
+
+    /// Store located in $CHALLENGES_INPUTS_DIR, or ./inputs if it isn't set
+    pub fn from_env() -> Self {
+        let root = std::env::var("CHALLENGES_INPUTS_DIR").unwrap_or(DEFAULT_INPUTS_DIR.to_owned());
+        return InputStore::new(Path::new(&root));
+    }
+
+    pub fn root(&self) -> &Path {
+        return &self.root;
+    }
+
+    pub fn path(&self, key: &ChallengeKey) -> PathBuf {
+        return self
+            .root
+            .join(&key.group_id)
+            .join(format!("{}.txt", key.challenge_id));
+    }
+
+    /// Path of the input saved by the first versions
+    fn legacy_path(&self, key: &ChallengeKey) -> PathBuf {
+        let (group_key, challenge_key) = key.legacy_keys();
+        return self
+            .root
+            .join(group_key)
+            .join(format!("{}.txt", challenge_key));
+    }
+
+    /// Input saved under the ids, or else under the keys of the first versions
+    pub fn load(&self, key: &ChallengeKey) -> Result<Option<String>> {
+        for path in [self.path(key), self.legacy_path(key)].iter() {
+            match fs::read_to_string(path) {
+                Ok(input) => return Ok(Some(input)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        return Ok(None);
+    }
+
+    /// Saves the input under the ids
+    pub fn save(&self, key: &ChallengeKey, input: &str) -> Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, input)?;
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_store(name: &str) -> InputStore {
+        let root = std::env::temp_dir().join(format!(
+            "challenges-input-store-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        return InputStore::new(&root);
+    }
+
+    fn key() -> ChallengeKey {
+        return ChallengeKey {
+            group_id: "group".to_owned(),
+            group_name: "My  Group".to_owned(),
+            challenge_id: "1".to_owned(),
+            challenge_title: "Day 1: Sum".to_owned(),
+        };
+    }
+
+    #[test]
+    fn path() {
+        let store = InputStore::new(Path::new("inputs"));
+        assert_eq!(store.path(&key()), Path::new("inputs/group/1.txt"));
+        assert_eq!(
+            store.legacy_path(&key()),
+            Path::new("inputs/my-group/day-1-sum.txt")
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let store = test_store("save-and-load");
+        assert_eq!(store.load(&key()).unwrap(), None);
+        store.save(&key(), "1\n2\n").unwrap();
+        assert_eq!(store.load(&key()).unwrap(), Some("1\n2\n".to_owned()));
+        assert!(store.path(&key()).is_file());
+        fs::remove_dir_all(store.root()).unwrap();
+    }
+
+    #[test]
+    fn load_legacy() {
+        let store = test_store("load-legacy");
+        let legacy_path = store.legacy_path(&key());
+        fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+        fs::write(&legacy_path, "legacy").unwrap();
+        assert_eq!(store.load(&key()).unwrap(), Some("legacy".to_owned()));
+        store.save(&key(), "new").unwrap();
+        assert_eq!(store.load(&key()).unwrap(), Some("new".to_owned()));
+        assert_eq!(fs::read_to_string(&legacy_path).unwrap(), "legacy");
+        fs::remove_dir_all(store.root()).unwrap();
+    }
+}
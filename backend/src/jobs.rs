@@ -0,0 +1,496 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+pub use challenges_types::jobs::{Job, JobStatus};
+
+use crate::groups::{
+    challenge_config::{ChallengeConfig, ChallengeError},
+    solution::Solution,
+    solve_context::SolveContext,
+};
+
+const DEFAULT_WORKERS: usize = 4;
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_RETENTION_SECS: u64 = 600;
+/// Finished jobs kept at most, whatever their age
+const DEFAULT_MAX_FINISHED: usize = 1000;
+/// Interval at which a running job checks whether it was cancelled or timed out
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+struct Task {
+    id: u64,
+    challenge: Box<dyn ChallengeConfig>,
+    input: String,
+    part: Option<usize>,
+    context: SolveContext,
+}
+
+struct JobEntry {
+    job: Job,
+    context: SolveContext,
+    finished_at: Option<Instant>,
+}
+
+impl JobEntry {
+    fn snapshot(&self) -> Job {
+        let mut job = self.job.clone();
+        if job.status == JobStatus::Running {
+            job.progress = Some(self.context.progress().report());
+        }
+        return job;
+    }
+
+    fn finish(&mut self, status: JobStatus) {
+        self.job.status = status;
+        self.finished_at = Some(Instant::now());
+    }
+}
+
+type Jobs = Arc<Mutex<HashMap<u64, JobEntry>>>;
+
+/// Solves challenges in the background on a fixed number of worker threads.
+/// A job that times out or is cancelled while running is reported right away, but its worker
+/// waits for the solver to stop, at its next cancellation check, before taking another job so
+/// that solvers ignoring the cancellation can't pile up. Finished jobs are forgotten after the
+/// retention, or sooner when too many of them finished
+pub struct JobManager {
+    jobs: Jobs,
+    next_id: AtomicU64,
+    sender: Mutex<Sender<Task>>,
+    timeout: Duration,
+    retention: Duration,
+    max_finished: usize,
+}
+
+impl JobManager {
+    pub fn new(workers: usize, timeout: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel::<Task>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let jobs: Jobs = Arc::new(Mutex::new(HashMap::new()));
+        for _ in 0..workers.max(1) {
+            let receiver = receiver.clone();
+            let jobs = jobs.clone();
+            thread::spawn(move || loop {
+                // The lock is released as soon as a task is received
+                let task = match receiver.lock().unwrap().recv() {
+                    Ok(t) => t,
+                    Err(_) => return,
+                };
+                run_task(&jobs, task, timeout);
+            });
+        }
+        return JobManager {
+            jobs: jobs,
+            next_id: AtomicU64::new(1),
+            sender: Mutex::new(sender),
+            timeout: timeout,
+            retention: Duration::from_secs(DEFAULT_RETENTION_SECS),
+            max_finished: DEFAULT_MAX_FINISHED,
+        };
+    }
+
+    /// Keeps the finished jobs for the retention, and at most `max_finished` of them
+    pub fn with_retention(mut self, retention: Duration, max_finished: usize) -> Self {
+        self.retention = retention;
+        self.max_finished = max_finished;
+        return self;
+    }
+
+    /// Manager using $CHALLENGES_JOB_WORKERS workers and a timeout of $CHALLENGES_JOB_TIMEOUT
+    /// seconds, keeping the finished jobs for $CHALLENGES_JOB_RETENTION seconds
+    pub fn from_env() -> Self {
+        let workers = std::env::var("CHALLENGES_JOB_WORKERS")
+            .ok()
+            .and_then(|w| w.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_WORKERS);
+        let timeout = std::env::var("CHALLENGES_JOB_TIMEOUT")
+            .ok()
+            .and_then(|t| t.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_TIMEOUT_SECS);
+        let retention = std::env::var("CHALLENGES_JOB_RETENTION")
+            .ok()
+            .and_then(|t| t.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_RETENTION_SECS);
+        return JobManager::new(workers, Duration::from_secs(timeout))
+            .with_retention(Duration::from_secs(retention), DEFAULT_MAX_FINISHED);
+    }
+
+    /// Queues a solve of the whole challenge, or of a single part, and returns the queued job
+    pub fn submit(
+        &self,
+        challenge: Box<dyn ChallengeConfig>,
+        input: String,
+        part: Option<usize>,
+    ) -> Result<Job> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let job = Job {
+            id: id,
+            status: JobStatus::Queued,
+            progress: None,
+            solution: None,
+            error: None,
+        };
+        let context = SolveContext::default();
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            prune(&mut jobs, self.retention, self.max_finished);
+            jobs.insert(
+                id,
+                JobEntry {
+                    job: job.clone(),
+                    context: context.clone(),
+                    finished_at: None,
+                },
+            );
+        }
+        let task = Task {
+            id: id,
+            challenge: challenge,
+            input: input,
+            part: part,
+            context: context,
+        };
+        if self.sender.lock().unwrap().send(task).is_err() {
+            self.jobs.lock().unwrap().remove(&id);
+            return Err(anyhow::anyhow!("The job workers have stopped"));
+        }
+        return Ok(job);
+    }
+
+    /// Time after which a running job fails
+    pub fn timeout(&self) -> Duration {
+        return self.timeout;
+    }
+
+    pub fn get(&self, id: u64) -> Option<Job> {
+        return self.jobs.lock().unwrap().get(&id).map(JobEntry::snapshot);
+    }
+
+    /// Cancels a queued or running job, finished jobs are left untouched
+    pub fn cancel(&self, id: u64) -> Option<Job> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let entry = jobs.get_mut(&id)?;
+        if !entry.job.status.is_finished() {
+            entry.finish(JobStatus::Cancelled);
+            entry.context.token().cancel();
+        }
+        return Some(entry.job.clone());
+    }
+}
+
+/// Forgets the jobs finished for longer than the retention, then the oldest finished jobs
+/// beyond `max_finished`
+fn prune(jobs: &mut HashMap<u64, JobEntry>, retention: Duration, max_finished: usize) {
+    jobs.retain(|_, e| e.finished_at.is_none_or(|t| t.elapsed() < retention));
+    let mut finished: Vec<(Instant, u64)> = jobs
+        .iter()
+        .filter_map(|(id, e)| e.finished_at.map(|t| (t, *id)))
+        .collect();
+    if finished.len() > max_finished {
+        finished.sort();
+        for (_, id) in &finished[..finished.len() - max_finished] {
+            jobs.remove(id);
+        }
+    }
+}
+
+/// Marks the job as running unless it was cancelled while queued
+fn start_job(jobs: &Jobs, id: u64) -> bool {
+    let mut jobs = jobs.lock().unwrap();
+    return match jobs.get_mut(&id) {
+        Some(entry) if entry.job.status == JobStatus::Queued => {
+            entry.job.status = JobStatus::Running;
+            true
+        }
+        _ => false,
+    };
+}
+
+/// Records the result of the solve unless the job was cancelled in the meantime
+fn finish_job(jobs: &Jobs, id: u64, result: Result<Solution, ChallengeError>) {
+    let mut jobs = jobs.lock().unwrap();
+    let entry = match jobs.get_mut(&id) {
+        Some(e) if !e.job.status.is_finished() => e,
+        _ => return,
+    };
+    match result {
+        Ok(solution) => {
+            entry.job.solution = Some(solution);
+            entry.finish(JobStatus::Done);
+        }
+        Err(e) => {
+            entry.job.error = Some(e.to_string());
+            entry.finish(JobStatus::Failed);
+        }
+    }
+}
+
+fn run_task(jobs: &Jobs, task: Task, timeout: Duration) {
+    let id = task.id;
+    let token = task.context.token().clone();
+    if !start_job(jobs, id) {
+        return;
+    }
+    let (sender, receiver) = mpsc::channel::<Result<Solution>>();
+    thread::spawn(move || {
+        let result = match task.part {
+            Some(p) => task.challenge.solve_part(&task.input, p, &task.context),
+            None => task.challenge.solve(&task.input, &task.context),
+        };
+        let _ = sender.send(result);
+    });
+    let start = Instant::now();
+    let result = loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(result) => break result,
+            Err(RecvTimeoutError::Disconnected) => {
+                break Err(ChallengeError::new("The solver panicked").into())
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if token.is_cancelled() {
+                    break Err(ChallengeError::cancelled().into());
+                }
+                if start.elapsed() >= timeout {
+                    token.cancel();
+                    break Err(ChallengeError::timed_out().into());
+                }
+            }
+        }
+    };
+    let stopped = result.is_err();
+    finish_job(
+        jobs,
+        id,
+        result.map_err(|e| match e.downcast::<ChallengeError>() {
+            Ok(e) => e,
+            Err(e) => ChallengeError::new(&e.to_string()),
+        }),
+    );
+    // The worker is only freed once the solver returns
+    if stopped {
+        let _ = receiver.recv();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Sleeps for the number of milliseconds given as input
+    struct TestChallenge {}
+
+    impl ChallengeConfig for TestChallenge {
+        fn title(&self) -> &str {
+            return "Test";
+        }
+
+        fn id(&self) -> String {
+            return "test".to_owned();
+        }
+
+        fn solve_part(&self, input: &str, part: usize, context: &SolveContext) -> Result<Solution> {
+            let millis = match input.parse::<u64>() {
+                Ok(m) => m,
+                Err(_) => return Err(ChallengeError::new("Invalid input").into()),
+            };
+            for i in 0..millis {
+                context.check()?;
+                context.progress().set_steps(i as usize, millis as usize);
+                thread::sleep(Duration::from_millis(1));
+            }
+            return Ok(Solution::for_part(part, millis));
+        }
+    }
+
+    /// Sleeps for the number of milliseconds given as input, without checking for cancellation
+    struct StubbornChallenge {}
+
+    impl ChallengeConfig for StubbornChallenge {
+        fn title(&self) -> &str {
+            return "Stubborn";
+        }
+
+        fn id(&self) -> String {
+            return "stubborn".to_owned();
+        }
+
+        fn solve_part(
+            &self,
+            input: &str,
+            part: usize,
+            _context: &SolveContext,
+        ) -> Result<Solution> {
+            let millis = input.parse::<u64>()?;
+            thread::sleep(Duration::from_millis(millis));
+            return Ok(Solution::for_part(part, millis));
+        }
+    }
+
+    fn wait(manager: &JobManager, id: u64) -> Job {
+        let start = Instant::now();
+        loop {
+            let job = manager.get(id).unwrap();
+            if job.status.is_finished() || start.elapsed() > Duration::from_secs(5) {
+                return job;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[rstest(
+        input,
+        part,
+        expected_status,
+        expected_parts,
+        case("0", None, JobStatus::Done, 2),
+        case("0", Some(2), JobStatus::Done, 1),
+        case("abc", None, JobStatus::Failed, 0),
+        case("2000", None, JobStatus::Failed, 0)
+    )]
+    fn submit(input: &str, part: Option<usize>, expected_status: JobStatus, expected_parts: usize) {
+        let manager = JobManager::new(1, Duration::from_millis(200));
+        let job = manager
+            .submit(Box::new(TestChallenge {}), input.to_owned(), part)
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+        let job = wait(&manager, job.id);
+        assert_eq!(job.status, expected_status);
+        assert_eq!(
+            job.solution.map(|s| s.parts().len()).unwrap_or_default(),
+            expected_parts
+        );
+        assert_eq!(job.error.is_some(), expected_status == JobStatus::Failed);
+    }
+
+    #[test]
+    fn cancel() {
+        let manager = JobManager::new(1, Duration::from_secs(5));
+        let running = manager
+            .submit(Box::new(TestChallenge {}), "300".to_owned(), None)
+            .unwrap();
+        let queued = manager
+            .submit(Box::new(TestChallenge {}), "0".to_owned(), None)
+            .unwrap();
+        assert_eq!(
+            manager.cancel(queued.id).unwrap().status,
+            JobStatus::Cancelled
+        );
+        assert_eq!(
+            manager.cancel(running.id).unwrap().status,
+            JobStatus::Cancelled
+        );
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(
+            manager.get(running.id).unwrap().status,
+            JobStatus::Cancelled
+        );
+        assert_eq!(manager.get(queued.id).unwrap().status, JobStatus::Cancelled);
+        assert!(manager.cancel(1000).is_none());
+    }
+
+    #[test]
+    fn stopped_solve_keeps_its_worker() {
+        let manager = JobManager::new(1, Duration::from_secs(5));
+        let stubborn = manager
+            .submit(Box::new(StubbornChallenge {}), "300".to_owned(), Some(1))
+            .unwrap();
+        while manager.get(stubborn.id).unwrap().status == JobStatus::Queued {
+            thread::sleep(Duration::from_millis(5));
+        }
+        manager.cancel(stubborn.id);
+        let next = manager
+            .submit(Box::new(TestChallenge {}), "0".to_owned(), Some(1))
+            .unwrap();
+        thread::sleep(Duration::from_millis(150));
+        assert_eq!(
+            manager.get(stubborn.id).unwrap().status,
+            JobStatus::Cancelled
+        );
+        assert_eq!(manager.get(next.id).unwrap().status, JobStatus::Queued);
+        assert_eq!(wait(&manager, next.id).status, JobStatus::Done);
+    }
+
+    #[test]
+    fn forgets_finished_jobs() {
+        let manager =
+            JobManager::new(1, Duration::from_secs(5)).with_retention(Duration::from_secs(60), 2);
+        let ids: Vec<u64> = (0..3)
+            .map(|_| {
+                let job = manager
+                    .submit(Box::new(TestChallenge {}), "0".to_owned(), Some(1))
+                    .unwrap();
+                wait(&manager, job.id);
+                job.id
+            })
+            .collect();
+        manager
+            .submit(Box::new(TestChallenge {}), "0".to_owned(), Some(1))
+            .unwrap();
+        assert!(manager.get(ids[0]).is_none());
+        assert!(manager.get(ids[1]).is_some());
+
+        let manager = JobManager::new(1, Duration::from_secs(5))
+            .with_retention(Duration::from_millis(50), 10);
+        let finished = manager
+            .submit(Box::new(TestChallenge {}), "0".to_owned(), Some(1))
+            .unwrap();
+        wait(&manager, finished.id);
+        let running = manager
+            .submit(Box::new(TestChallenge {}), "300".to_owned(), Some(1))
+            .unwrap();
+        thread::sleep(Duration::from_millis(100));
+        let last = manager
+            .submit(Box::new(TestChallenge {}), "0".to_owned(), Some(1))
+            .unwrap();
+        assert!(manager.get(finished.id).is_none());
+        assert!(manager.get(running.id).is_some());
+        assert!(manager.get(last.id).is_some());
+    }
+
+    #[rstest(
+        input,
+        expected_solution,
+        expected_error,
+        case("0", Some(Solution::for_part(1, 0)), None),
+        case("abc", None, Some(ChallengeError::new("Invalid input"))),
+        case("2000", None, Some(ChallengeError::timed_out()))
+    )]
+    fn outcome(
+        input: &str,
+        expected_solution: Option<Solution>,
+        expected_error: Option<ChallengeError>,
+    ) {
+        let manager = JobManager::new(1, Duration::from_millis(200));
+        let job = manager
+            .submit(Box::new(TestChallenge {}), input.to_owned(), Some(1))
+            .unwrap();
+        let job = wait(&manager, job.id);
+        assert_eq!(job.solution, expected_solution);
+        assert_eq!(job.error, expected_error.map(|e| e.to_string()));
+        assert!(manager.get(1000).is_none());
+    }
+
+    #[test]
+    fn progress() {
+        let manager = JobManager::new(1, Duration::from_secs(5));
+        let job = manager
+            .submit(Box::new(TestChallenge {}), "100".to_owned(), Some(1))
+            .unwrap();
+        thread::sleep(Duration::from_millis(50));
+        let running = manager.get(job.id).unwrap();
+        assert_eq!(running.status, JobStatus::Running);
+        assert!(running.progress.is_some());
+        assert_eq!(wait(&manager, job.id).status, JobStatus::Done);
+    }
+}
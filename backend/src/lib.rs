@@ -1,5 +1,12 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod answers;
+/// Bodies of the API responses that aren't types of the library
+pub mod api;
+pub mod bench;
+pub mod config;
 pub mod groups;
+pub mod input_store;
+pub mod jobs;
 pub mod utils;
@@ -1,3 +1,5 @@
+use regex::Regex;
+
 pub trait InputUtils {
     fn split_sections(&self) -> Vec<String>;
 }
@@ -12,3 +14,94 @@ impl InputUtils for str {
             .collect();
     }
 }
+
+/// Key of a group or challenge in the first versions of the API, which saved inputs and answers
+/// are still named after. Runs of other characters only lose one dash, so it may keep several
+/// of them and two names may share it, `slugify` gives the ids
+pub fn create_key(display_name: &str) -> String {
+    lazy_static! {
+        static ref KEY_REGEX: Regex = Regex::new(r"[^A-Za-z0-9]").unwrap();
+    }
+    return KEY_REGEX
+        .replace_all(display_name, "-")
+        .to_lowercase()
+        .replace("--", "-");
+}
+
+/// Id of a group or challenge derived from its display name: the lowercase ASCII letters and
+/// digits, each run of other characters becoming a single dash, without leading or trailing dash
+pub fn slugify(display_name: &str) -> String {
+    let mut slug = String::new();
+    let mut separated = false;
+    for c in display_name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            separated = true;
+            continue;
+        }
+        if separated && !slug.is_empty() {
+            slug.push('-');
+        }
+        separated = false;
+        slug.push(c.to_ascii_lowercase());
+    }
+    return slug;
+}
+
+/// Whether the id is already a slug, i.e. is made of lowercase letters and digits separated by
+/// single dashes, usable in constant expressions
+pub const fn is_slug(id: &str) -> bool {
+    let id = id.as_bytes();
+    if id.is_empty() || id[0] == b'-' || id[id.len() - 1] == b'-' {
+        return false;
+    }
+    let mut i = 0;
+    while i < id.len() {
+        let valid = match id[i] {
+            b'a'..=b'z' | b'0'..=b'9' => true,
+            b'-' => id[i - 1] != b'-',
+            _ => false,
+        };
+        if !valid {
+            return false;
+        }
+        i += 1;
+    }
+    return true;
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        name,
+        expected,
+        case("Day 1: Report Repair", "day-1-report-repair"),
+        case("a - b", "a-b"),
+        case("a---b", "a-b"),
+        case("  Advent of Code 2020!", "advent-of-code-2020"),
+        case("Day 1: Été", "day-1-t"),
+        case("--", "")
+    )]
+    fn slugify_collapses_separators(name: &str, expected: &str) {
+        assert_eq!(slugify(name), expected);
+    }
+
+    #[rstest(
+        id,
+        expected,
+        case("day-1-report-repair", true),
+        case("day1", true),
+        case("Day-1", false),
+        case("day--1", false),
+        case("-day-1", false),
+        case("day-1-", false),
+        case("day 1", false),
+        case("", false)
+    )]
+    fn slugs(id: &str, expected: bool) {
+        assert_eq!(is_slug(id), expected);
+    }
+}
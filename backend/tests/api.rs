@@ -0,0 +1,465 @@
+use std::{
+    env, fs,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    process::{self, Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use challenges::{
+    answers::PartStatus,
+    api::ItemName,
+    groups::{health::HealthStatus, solution::Solution},
+    jobs::{Job, JobStatus},
+};
+use challenges_client::{Client, ClientError};
+use serde_json::{json, Value};
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+const GROUP: &str = "advent-of-code-2020";
+const CHALLENGE: &str = "day-1-report-repair";
+const INPUT: &str = "1721\n979\n366\n299\n675\n1456";
+
+fn free_port() -> u16 {
+    return TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+}
+
+/// API binary serving the 2020 group and a remote group whose service is down, on a free port
+/// with its own inputs directory. The process is stopped once dropped
+struct Server {
+    process: Child,
+    dir: PathBuf,
+    port: u16,
+    client: Client,
+}
+
+impl Server {
+    fn launch(name: &str) -> Server {
+        let dir = env::temp_dir().join(format!("challenges-api-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("challenges.toml");
+        fs::write(
+            &config,
+            format!(
+                r#"
+                builtin_groups = ["Advent of Code 2020"]
+
+                [[remote_groups]]
+                name = "Down - Service"
+                url = ""
+                base_url = "http://127.0.0.1:{}"
+                list_retries = 0
+                "#,
+                free_port()
+            ),
+        )
+        .unwrap();
+        let port = free_port();
+        let process = Command::new(env!("CARGO_BIN_EXE_api"))
+            .env("CHALLENGES_CONFIG", &config)
+            .env("CHALLENGES_API_PORT", port.to_string())
+            .env("CHALLENGES_INPUTS_DIR", dir.join("inputs"))
+            .env("CHALLENGES_ANSWERS_FILE", dir.join("answers.json"))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let server = Server {
+            process: process,
+            dir: dir,
+            port: port,
+            client: Client::new(&format!("http://127.0.0.1:{}/api", port)).unwrap(),
+        };
+        let started = Instant::now();
+        while server.client.groups().is_err() {
+            assert!(started.elapsed() < STARTUP_TIMEOUT, "The API didn't start");
+            thread::sleep(Duration::from_millis(100));
+        }
+        return server;
+    }
+}
+
+impl Server {
+    /// Sends a request under `/api` without going through the client, returning the status and
+    /// the JSON body of the response, `null` if it has none
+    fn request(&self, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(("127.0.0.1", self.port)).unwrap();
+        write!(
+            stream,
+            "{} /api{} HTTP/1.0\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        let value = if body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(body).unwrap_or_else(|e| panic!("{} {}: {}", method, path, e))
+        };
+        return (status, value);
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn groups() {
+    let server = Server::launch("groups");
+    let client = &server.client;
+
+    let groups = client.groups().unwrap();
+    let keys: Vec<&str> = groups.iter().map(|g| g.key.as_str()).collect();
+    assert_eq!(keys, vec!["down-service", GROUP]);
+    assert_eq!(groups[0].health.status, HealthStatus::Down);
+    assert_eq!(groups[1].health.status, HealthStatus::Up);
+
+    let group = client.group(GROUP).unwrap().unwrap();
+    assert_eq!(group.name, "Advent of Code 2020");
+    assert_eq!(
+        group.challenges[0],
+        ItemName {
+            key: CHALLENGE.to_owned(),
+            display_name: "Day 1: Report Repair".to_owned(),
+        }
+    );
+    assert_eq!(client.group("unknown").unwrap(), None);
+
+    let challenge = client.challenge(GROUP, CHALLENGE).unwrap().unwrap();
+    assert_eq!(challenge.title, "Day 1: Report Repair");
+    assert_eq!(client.challenge(GROUP, "day-99").unwrap(), None);
+
+    // "down--service" is the key of the first versions, redirected to "down-service"
+    match client.group("down--service") {
+        Err(ClientError::Unavailable(e)) => assert_eq!(e.group, "Down - Service"),
+        other => panic!("Expected the group to be unavailable, got {:?}", other),
+    }
+
+    let results = client.search("report repair").unwrap();
+    assert_eq!(results[0].group_key, GROUP);
+    assert_eq!(results[0].challenge_key, CHALLENGE);
+}
+
+/// Solution of the job once it is finished
+fn solution(client: &Client, job: Job) -> Option<Solution> {
+    return client
+        .wait(job, Duration::from_millis(50))
+        .unwrap()
+        .unwrap()
+        .solution;
+}
+
+#[test]
+fn solve() {
+    let server = Server::launch("solve");
+    let client = &server.client;
+
+    let job = client.solve(GROUP, CHALLENGE, INPUT, None).unwrap();
+    assert_eq!(job.status, JobStatus::Queued);
+    assert_eq!(
+        solution(client, job),
+        Some(Solution::for_part(1, 514579).merge(Solution::for_part(2, 241861950)))
+    );
+    let job = client.solve(GROUP, CHALLENGE, INPUT, Some(1)).unwrap();
+    assert_eq!(solution(client, job), Some(Solution::for_part(1, 514579)));
+    assert!(matches!(
+        client.solve(GROUP, "day-99", INPUT, None),
+        Err(ClientError::NotFound)
+    ));
+
+    assert!(matches!(
+        client.solve_saved(GROUP, CHALLENGE, None),
+        Err(ClientError::NotFound)
+    ));
+    client.save_input(GROUP, CHALLENGE, INPUT).unwrap();
+    let job = client.solve_saved(GROUP, CHALLENGE, Some(2)).unwrap();
+    assert_eq!(
+        solution(client, job),
+        Some(Solution::for_part(2, 241861950))
+    );
+
+    assert!(matches!(
+        client.bench(1000000, Some(GROUP)),
+        Err(ClientError::Failed(status, _)) if status.as_u16() == 400
+    ));
+
+    let verifications = client.verify(Some(GROUP)).unwrap();
+    assert_eq!(verifications.len(), 1);
+    assert_eq!(verifications[0].challenge, "Day 1: Report Repair");
+    assert!(verifications[0]
+        .parts
+        .iter()
+        .all(|p| p.status == PartStatus::Missing));
+
+    let job = client.solve(GROUP, CHALLENGE, INPUT, Some(1)).unwrap();
+    let job = client
+        .wait(job, Duration::from_millis(50))
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.status, JobStatus::Done);
+    assert_eq!(client.job(job.id).unwrap(), Some(job.clone()));
+    assert_eq!(client.job(job.id + 1).unwrap(), None);
+}
+
+/// Operations of the API as `(method, path, parameters, schema of the success response)`, the
+/// parameters being `<name> in <location>` and `[]` marking an array
+const OPERATIONS: &[(&str, &str, &[&str], Option<&str>)] = &[
+    ("get", "/groups", &[], Some("GroupSummary[]")),
+    ("post", "/refresh", &[], Some("GroupSummary[]")),
+    (
+        "get",
+        "/groups/{group_key}",
+        &["group_key in path"],
+        Some("Group"),
+    ),
+    (
+        "get",
+        "/groups/{group_key}/{challenge_key}",
+        &["challenge_key in path", "group_key in path"],
+        Some("Challenge"),
+    ),
+    (
+        "post",
+        "/groups/{group_key}/{challenge_key}/solve",
+        &[
+            "challenge_key in path",
+            "group_key in path",
+            "part in query",
+            "use_saved in query",
+        ],
+        Some("Job"),
+    ),
+    (
+        "put",
+        "/groups/{group_key}/{challenge_key}/input",
+        &["challenge_key in path", "group_key in path"],
+        None,
+    ),
+    ("get", "/jobs/{id}", &["id in path"], Some("Job")),
+    ("delete", "/jobs/{id}", &["id in path"], Some("Job")),
+    ("get", "/jobs/{id}/events", &["id in path"], Some("Job")),
+    (
+        "get",
+        "/bench",
+        &["group in query", "runs in query"],
+        Some("BenchReport"),
+    ),
+    (
+        "get",
+        "/verify",
+        &["group in query"],
+        Some("ChallengeVerification[]"),
+    ),
+    ("get", "/search", &["q in query"], Some("SearchResult[]")),
+    ("get", "/openapi.json", &[], None),
+];
+
+/// Name of a referenced schema, with `[]` for an array of them
+fn schema_name(schema: &Value) -> Option<String> {
+    if let Some(items) = schema.get("items") {
+        return schema_name(items).map(|n| format!("{}[]", n));
+    }
+    return schema["$ref"]
+        .as_str()
+        .map(|r| r.trim_start_matches("#/components/schemas/").to_owned());
+}
+
+/// Operations described by the document, in the format of `OPERATIONS`
+fn documented_operations(document: &Value) -> Vec<(String, String, Vec<String>, Option<String>)> {
+    let mut operations = vec![];
+    for (path, methods) in document["paths"].as_object().unwrap() {
+        for (method, operation) in methods.as_object().unwrap() {
+            let mut parameters: Vec<String> = operation["parameters"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|p| {
+                    format!(
+                        "{} in {}",
+                        p["name"].as_str().unwrap(),
+                        p["in"].as_str().unwrap()
+                    )
+                })
+                .collect();
+            parameters.sort();
+            let success = operation["responses"]["200"]["content"]
+                .as_object()
+                .and_then(|c| c.values().next())
+                .and_then(|c| schema_name(&c["schema"]));
+            operations.push((method.clone(), path.clone(), parameters, success));
+        }
+    }
+    operations.sort();
+    return operations;
+}
+
+/// Checks that the value has the shape of the schema: the types, the enums, the required
+/// properties and no undocumented property
+fn check_schema(document: &Value, schema: &Value, value: &Value, at: &str) -> Result<(), String> {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/components/schemas/");
+        let schema = &document["components"]["schemas"][name];
+        if schema.is_null() {
+            return Err(format!("{}: unknown schema {}", at, name));
+        }
+        return check_schema(document, schema, value, at);
+    }
+    if let Some(variants) = schema["oneOf"].as_array() {
+        if variants
+            .iter()
+            .any(|v| check_schema(document, v, value, at).is_ok())
+        {
+            return Ok(());
+        }
+        return Err(format!("{}: {} matches none of {}", at, value, schema));
+    }
+    if let Some(allowed) = schema["enum"].as_array() {
+        if !allowed.contains(value) {
+            return Err(format!("{}: {} isn't one of {:?}", at, value, allowed));
+        }
+    }
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(t) => vec![t.as_str()],
+        Value::Array(t) => t.iter().map(|t| t.as_str().unwrap()).collect(),
+        _ => return Ok(()),
+    };
+    let matches = types.iter().any(|t| match *t {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    });
+    if !matches {
+        return Err(format!("{}: {} isn't of type {:?}", at, value, types));
+    }
+    if let Some(items) = value.as_array() {
+        for (i, item) in items.iter().enumerate() {
+            check_schema(document, &schema["items"], item, &format!("{}[{}]", at, i))?;
+        }
+    }
+    if let Some(object) = value.as_object() {
+        for required in schema["required"].as_array().into_iter().flatten() {
+            if !object.contains_key(required.as_str().unwrap()) {
+                return Err(format!("{}: {} is missing", at, required));
+            }
+        }
+        for (key, property) in object {
+            let at = format!("{}.{}", at, key);
+            match (&schema["properties"][key], &schema["additionalProperties"]) {
+                (Value::Null, Value::Null) => return Err(format!("{} isn't documented", at)),
+                (Value::Null, additional) => check_schema(document, additional, property, &at)?,
+                (documented, _) => check_schema(document, documented, property, &at)?,
+            }
+        }
+    }
+    return Ok(());
+}
+
+#[test]
+fn openapi() {
+    let server = Server::launch("openapi");
+    let document = server.client.openapi().unwrap();
+
+    assert!(document["openapi"].as_str().unwrap().starts_with("3."));
+    let mut expected: Vec<(String, String, Vec<String>, Option<String>)> = OPERATIONS
+        .iter()
+        .map(|(method, path, parameters, success)| {
+            let mut parameters: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
+            parameters.sort();
+            (
+                method.to_string(),
+                path.to_string(),
+                parameters,
+                success.map(str::to_owned),
+            )
+        })
+        .collect();
+    expected.sort();
+    assert_eq!(documented_operations(&document), expected);
+
+    // The responses of the routes have the shape of their documented schemas
+    let solve_path = format!("/groups/{}/{}/solve", GROUP, CHALLENGE);
+    let (_, job) = server.request("POST", &solve_path, INPUT);
+    let job_path = format!("/jobs/{}", job["id"]);
+    server.request(
+        "PUT",
+        &format!("/groups/{}/{}/input", GROUP, CHALLENGE),
+        INPUT,
+    );
+    for (method, path, body, status, schema) in vec![
+        ("GET", "/groups".to_owned(), "", 200, "GroupSummary[]"),
+        ("POST", "/refresh".to_owned(), "", 200, "GroupSummary[]"),
+        ("GET", format!("/groups/{}", GROUP), "", 200, "Group"),
+        (
+            "GET",
+            format!("/groups/{}/{}", GROUP, CHALLENGE),
+            "",
+            200,
+            "Challenge",
+        ),
+        ("POST", format!("{}?part=2", solve_path), INPUT, 200, "Job"),
+        ("GET", job_path.clone(), "", 200, "Job"),
+        ("DELETE", job_path, "", 200, "Job"),
+        (
+            "GET",
+            format!("/bench?runs=1&group={}", GROUP),
+            "",
+            200,
+            "BenchReport",
+        ),
+        (
+            "GET",
+            "/verify".to_owned(),
+            "",
+            200,
+            "ChallengeVerification[]",
+        ),
+        (
+            "GET",
+            "/search?q=report".to_owned(),
+            "",
+            200,
+            "SearchResult[]",
+        ),
+        (
+            "GET",
+            "/groups/down-service".to_owned(),
+            "",
+            502,
+            "GroupUnavailableError",
+        ),
+    ] {
+        let (actual_status, value) = server.request(method, &path, body);
+        assert_eq!(actual_status, status, "{} {}", method, path);
+        let schema = match schema.strip_suffix("[]") {
+            Some(items) => {
+                json!({"type": "array", "items": {"$ref": format!("#/components/schemas/{}", items)}})
+            }
+            None => json!({ "$ref": format!("#/components/schemas/{}", schema) }),
+        };
+        if let Err(e) = check_schema(&document, &schema, &value, &format!("{} {}", method, path)) {
+            panic!("{}", e);
+        }
+    }
+}
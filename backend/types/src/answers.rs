@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::solution::{PartValue, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PartStatus {
+    Pass,
+    Fail,
+    Missing,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PartVerification {
+    pub part: String,
+    pub status: PartStatus,
+    pub expected: Option<PartValue>,
+    pub actual: Option<PartValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChallengeVerification {
+    pub group: String,
+    pub challenge: String,
+    pub parts: Vec<PartVerification>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ChallengeVerification {
+    /// Compares the result of solving a challenge with its known answers
+    pub fn new(
+        group_name: &str,
+        challenge_name: &str,
+        result: Result<Solution>,
+        expected: Option<&HashMap<String, PartValue>>,
+    ) -> Self {
+        let empty = HashMap::new();
+        let expected = expected.unwrap_or(&empty);
+        let mut parts = vec![];
+        let mut error = None;
+        match result {
+            Ok(solution) => {
+                for part in solution.parts() {
+                    let expected_value = expected.get(&part.name);
+                    let status = match expected_value {
+                        Some(v) if v.to_string() == part.value.to_string() => PartStatus::Pass,
+                        Some(_) => PartStatus::Fail,
+                        None => PartStatus::Missing,
+                    };
+                    parts.push(PartVerification {
+                        part: part.name.clone(),
+                        status: status,
+                        expected: expected_value.cloned(),
+                        actual: Some(part.value.clone()),
+                    });
+                }
+            }
+            Err(e) => error = Some(e.to_string()),
+        }
+        // Known answers that were not returned by the challenge
+        let mut remaining = expected
+            .iter()
+            .filter(|(name, _)| !parts.iter().any(|p| &&p.part == name))
+            .collect::<Vec<_>>();
+        remaining.sort_by_key(|(name, _)| name.to_owned());
+        for (name, value) in remaining {
+            parts.push(PartVerification {
+                part: name.clone(),
+                status: PartStatus::Fail,
+                expected: Some(value.clone()),
+                actual: None,
+            });
+        }
+        return ChallengeVerification {
+            group: group_name.to_owned(),
+            challenge: challenge_name.to_owned(),
+            parts: parts,
+            error: error,
+        };
+    }
+
+    /// Fails if any part fails, passes if all parts pass, missing otherwise
+    pub fn status(&self) -> PartStatus {
+        if self.error.is_some() || self.parts.iter().any(|p| p.status == PartStatus::Fail) {
+            return PartStatus::Fail;
+        }
+        if !self.parts.is_empty() && self.parts.iter().all(|p| p.status == PartStatus::Pass) {
+            return PartStatus::Pass;
+        }
+        return PartStatus::Missing;
+    }
+}
@@ -0,0 +1,112 @@
+use std::{fs, path::Path};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PartTiming {
+    /// Part number starting at 1, or 0 for the whole solve of challenges solving their parts
+    /// together
+    pub part: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+impl PartTiming {
+    /// Timings of the part from the durations of its runs, in milliseconds
+    pub fn from_samples(part: usize, mut samples: Vec<f64>) -> Self {
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2.0
+        } else {
+            samples[middle]
+        };
+        return PartTiming {
+            part: part,
+            min_ms: samples[0],
+            median_ms: median,
+            max_ms: samples[samples.len() - 1],
+        };
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChallengeBenchmark {
+    pub group: String,
+    pub challenge: String,
+    /// Absent from the reports of the first versions, which are compared by name
+    #[serde(default)]
+    pub group_id: String,
+    #[serde(default)]
+    pub challenge_id: String,
+    pub parts: Vec<PartTiming>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Regression {
+    pub group: String,
+    pub challenge: String,
+    pub part: usize,
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BenchReport {
+    pub runs: usize,
+    pub challenges: Vec<ChallengeBenchmark>,
+}
+
+impl BenchReport {
+    pub fn load(path: &Path) -> Result<Self> {
+        return Ok(serde_json::from_str(&fs::read_to_string(path)?)?);
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        return Ok(());
+    }
+
+    /// Lists the parts whose median got slower than the baseline by more than the given ratio (0.2 = 20%).
+    /// The challenges are matched by id, or by name when the baseline has no ids
+    pub fn compare(&self, baseline: &BenchReport, threshold: f64) -> Vec<Regression> {
+        let mut regressions = vec![];
+        for challenge in &self.challenges {
+            let baseline_challenge = match baseline.challenges.iter().find(|c| {
+                if c.group_id.is_empty() || c.challenge_id.is_empty() {
+                    return c.group == challenge.group && c.challenge == challenge.challenge;
+                }
+                return c.group_id == challenge.group_id
+                    && c.challenge_id == challenge.challenge_id;
+            }) {
+                Some(c) => c,
+                None => continue,
+            };
+            for part in &challenge.parts {
+                let baseline_part = match baseline_challenge
+                    .parts
+                    .iter()
+                    .find(|p| p.part == part.part)
+                {
+                    Some(p) => p,
+                    None => continue,
+                };
+                if part.median_ms > baseline_part.median_ms * (1.0 + threshold) {
+                    regressions.push(Regression {
+                        group: challenge.group.clone(),
+                        challenge: challenge.challenge.clone(),
+                        part: part.part,
+                        baseline_ms: baseline_part.median_ms,
+                        current_ms: part.median_ms,
+                    });
+                }
+            }
+        }
+        return regressions;
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::health::GroupHealth;
+
+/// Group or challenge as listed, `key` being its id in the API paths
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ItemName {
+    pub key: String,
+    pub display_name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct GroupSummary {
+    pub key: String,
+    pub display_name: String,
+    pub health: GroupHealth,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Group {
+    pub name: String,
+    pub url: String,
+    pub challenges: Vec<ItemName>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Challenge {
+    pub title: String,
+    pub description: String,
+}
+
+/// The challenges of a group can't be listed, e.g. because its service is down
+#[derive(Debug, Clone, PartialEq, Error, Serialize, Deserialize, ToSchema)]
+pub struct GroupUnavailableError {
+    pub group: String,
+    pub message: String,
+}
+
+impl GroupUnavailableError {
+    pub fn new(group: &str, message: &str) -> GroupUnavailableError {
+        return GroupUnavailableError {
+            group: group.to_owned(),
+            message: message.to_owned(),
+        };
+    }
+}
+
+impl fmt::Display for GroupUnavailableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is unavailable: {}", self.group, self.message)
+    }
+}
+
+/// Challenge matching a search, with the ids used by the API
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SearchResult {
+    pub group_key: String,
+    pub group: String,
+    pub challenge_key: String,
+    pub challenge: String,
+    pub tags: Vec<String>,
+    pub score: u32,
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The challenges of the group are available and up to date
+    Up,
+    /// The last listing failed, the challenges of a previous listing are still served
+    Degraded,
+    /// No challenges could be listed
+    Down,
+}
+
+/// Availability of the challenges of a group
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct GroupHealth {
+    pub status: HealthStatus,
+    /// Error of the last listing, kept until a listing succeeds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl GroupHealth {
+    pub fn up() -> Self {
+        return GroupHealth {
+            status: HealthStatus::Up,
+            last_error: None,
+        };
+    }
+}
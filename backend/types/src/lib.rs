@@ -0,0 +1,11 @@
+// Types of the API requests and responses, shared by the backend and the `challenges-client`
+// crate so that both sides agree on them when compiling, without the client depending on the
+// groups and the servers of the backend
+
+pub mod answers;
+pub mod bench;
+pub mod groups;
+pub mod health;
+pub mod jobs;
+pub mod progress;
+pub mod solution;
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Last progress reported by a solver
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ProgressReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+    /// Completion of the current phase, between 0 and 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fraction: Option<f64>,
+}
+
+impl ProgressReport {
+    /// Short description such as "Part 2 - 45%"
+    pub fn describe(&self) -> String {
+        let fraction = self.fraction.map(|f| format!("{:.0}%", f * 100.0));
+        return match (&self.phase, fraction) {
+            (Some(phase), Some(fraction)) => format!("{} - {}", phase, fraction),
+            (Some(phase), None) => phase.clone(),
+            (None, Some(fraction)) => fraction,
+            (None, None) => String::new(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest(
+        phase,
+        fraction,
+        expected,
+        case(Some("Part 2"), Some(0.451), "Part 2 - 45%"),
+        case(Some("Part 2"), None, "Part 2"),
+        case(None, Some(1.0), "100%"),
+        case(None, None, "")
+    )]
+    fn describe(phase: Option<&str>, fraction: Option<f64>, expected: &str) {
+        let report = ProgressReport {
+            phase: phase.map(str::to_owned),
+            fraction: fraction,
+        };
+        assert_eq!(report.describe(), expected);
+    }
+}
@@ -0,0 +1,326 @@
+use std::{convert::TryFrom, fmt};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use utoipa::ToSchema;
+
+/// The answer to a single part of a challenge
+#[derive(Clone, Debug, PartialEq, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum PartValue {
+    Integer(i128),
+    Text(String),
+}
+
+/// Largest integer up to which floats are exact
+const MAX_EXACT_FLOAT: f64 = (1_u64 << 53) as f64;
+
+// Untagged enums can't be deserialized from i128 values, visit the JSON types instead.
+// serde_json reads the integers that don't fit in 64 bits as floats, which are only accepted
+// when they hold an exact integer
+impl<'de> Deserialize<'de> for PartValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PartValueVisitor;
+
+        impl<'de> Visitor<'de> for PartValueVisitor {
+            type Value = PartValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an integer or a string")
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<PartValue, E> {
+                return Ok(PartValue::Integer(value as i128));
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<PartValue, E> {
+                return Ok(PartValue::Integer(value as i128));
+            }
+
+            fn visit_i128<E: de::Error>(self, value: i128) -> Result<PartValue, E> {
+                return Ok(PartValue::Integer(value));
+            }
+
+            fn visit_u128<E: de::Error>(self, value: u128) -> Result<PartValue, E> {
+                return match i128::try_from(value) {
+                    Ok(i) => Ok(PartValue::Integer(i)),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Other("u128"), &self)),
+                };
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<PartValue, E> {
+                if value.fract() != 0.0 || value.abs() > MAX_EXACT_FLOAT {
+                    return Err(E::invalid_value(de::Unexpected::Float(value), &self));
+                }
+                return Ok(PartValue::Integer(value as i128));
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<PartValue, E> {
+                return Ok(PartValue::Text(value.to_owned()));
+            }
+        }
+
+        return deserializer.deserialize_any(PartValueVisitor);
+    }
+}
+
+impl fmt::Display for PartValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartValue::Integer(i) => write!(f, "{}", i),
+            PartValue::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! part_value_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for PartValue {
+                fn from(value: $t) -> Self {
+                    return PartValue::Integer(value as i128);
+                }
+            }
+        )*
+    };
+}
+
+part_value_from_int!(i32, i64, i128, isize, u32, u64, usize);
+
+impl From<String> for PartValue {
+    fn from(value: String) -> Self {
+        return PartValue::Text(value);
+    }
+}
+
+impl From<&str> for PartValue {
+    fn from(value: &str) -> Self {
+        return PartValue::Text(value.to_owned());
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Part {
+    pub name: String,
+    pub value: PartValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)?;
+        if let Some(notes) = &self.notes {
+            write!(f, " ({})", notes)?;
+        }
+        return Ok(());
+    }
+}
+
+/// The ordered list of answers returned by a challenge
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Solution {
+    parts: Vec<Part>,
+}
+
+impl Solution {
+    pub fn new() -> Self {
+        return Solution { parts: vec![] };
+    }
+
+    /// Solution holding only the answer of the given part
+    pub fn for_part<V: Into<PartValue>>(part: usize, value: V) -> Self {
+        return Solution::new().with_part(format!("Part {}", part).as_str(), value);
+    }
+
+    pub fn from_parts(parts: Vec<Part>) -> Self {
+        return Solution { parts: parts };
+    }
+
+    pub fn with_part<V: Into<PartValue>>(self, name: &str, value: V) -> Self {
+        return self.push_part(name, value.into(), None);
+    }
+
+    pub fn with_part_notes<V: Into<PartValue>>(self, name: &str, value: V, notes: &str) -> Self {
+        return self.push_part(name, value.into(), Some(notes.to_owned()));
+    }
+
+    fn push_part(mut self, name: &str, value: PartValue, notes: Option<String>) -> Self {
+        self.parts.push(Part {
+            name: name.to_owned(),
+            value: value,
+            notes: notes,
+        });
+        return self;
+    }
+
+    pub fn merge(mut self, other: Solution) -> Self {
+        self.parts.extend(other.parts);
+        return self;
+    }
+
+    pub fn parts(&self) -> &Vec<Part> {
+        return &self.parts;
+    }
+
+    pub fn part(&self, name: &str) -> Option<&Part> {
+        return self.parts.iter().find(|p| p.name == name);
+    }
+
+    /// Solution holding only the given part, starting at 1, along with its notes
+    pub fn nth_part(&self, part: usize) -> Option<Solution> {
+        return self
+            .parts
+            .get(part.wrapping_sub(1))
+            .map(|p| Solution::from_parts(vec![p.clone()]));
+    }
+
+    /// Parses the "Name: value" lines returned by the other language modules.
+    /// Lines that do not follow that format are kept as notes of the previous part
+    pub fn from_text(text: &str) -> Self {
+        let mut solution = Solution::new();
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let split = line.splitn(2, ": ").collect::<Vec<_>>();
+            if split.len() == 2 {
+                let value = match split[1].parse::<i128>() {
+                    Ok(i) => PartValue::Integer(i),
+                    Err(_) => PartValue::Text(split[1].to_owned()),
+                };
+                solution = solution.push_part(split[0], value, None);
+                continue;
+            }
+            match solution.parts.last_mut() {
+                Some(part) => {
+                    part.notes = Some(match &part.notes {
+                        Some(notes) => format!("{}\n{}", notes, line),
+                        None => line.to_owned(),
+                    })
+                }
+                None => solution = solution.with_part("Result", line),
+            }
+        }
+        return solution;
+    }
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lines = self.parts.iter().map(Part::to_string).collect::<Vec<_>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn display() {
+        let solution = Solution::new()
+            .with_part("Part 1", 12)
+            .with_part_notes("Part 2", 6_usize, "2 * 3")
+            .with_part("Part 3", "abc");
+        assert_eq!(
+            solution.to_string(),
+            "Part 1: 12\nPart 2: 6 (2 * 3)\nPart 3: abc"
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let solution = Solution::new()
+            .with_part("Part 1", 12)
+            .with_part_notes("Part 2", "abc", "note");
+        assert_eq!(
+            serde_json::to_string(&solution).unwrap(),
+            r#"{"parts":[{"name":"Part 1","value":12},{"name":"Part 2","value":"abc","notes":"note"}]}"#
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let solution: Solution = serde_json::from_str(
+            r#"{"parts":[{"name":"Part 1","value":12},{"name":"Part 2","value":"abc","notes":"note"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            solution,
+            Solution::new()
+                .with_part("Part 1", 12)
+                .with_part_notes("Part 2", "abc", "note")
+        );
+    }
+
+    #[rstest(
+        json,
+        expected,
+        case("9223372036854775807", Some(PartValue::Integer(i64::MAX as i128))),
+        case("-9223372036854775808", Some(PartValue::Integer(i64::MIN as i128))),
+        case("18446744073709551615", Some(PartValue::Integer(u64::MAX as i128))),
+        case("12.0", Some(PartValue::Integer(12))),
+        case("-9007199254740992.0", Some(PartValue::Integer(-(1 << 53)))),
+        case("1.5", None),
+        case("18446744073709551616", None),
+        case("1e30", None),
+        case("170141183460469231731687303715884105727", None),
+        case("true", None)
+    )]
+    fn deserialize_number(json: &str, expected: Option<PartValue>) {
+        assert_eq!(serde_json::from_str::<PartValue>(json).ok(), expected);
+    }
+
+    #[rstest(value, case(i128::MIN), case(i128::MAX), case(0))]
+    fn roundtrip_i128(value: i128) {
+        let json = serde_json::to_string(&PartValue::from(value)).unwrap();
+        assert_eq!(json, value.to_string());
+        let deserializer =
+            de::value::I128Deserializer::<de::value::Error>::new(json.parse().unwrap());
+        assert_eq!(
+            PartValue::deserialize(deserializer).unwrap(),
+            PartValue::Integer(value)
+        );
+    }
+
+    #[rstest(
+        value,
+        expected,
+        case(i128::MAX as u128, Some(PartValue::Integer(i128::MAX))),
+        case(i128::MAX as u128 + 1, None),
+        case(u128::MAX, None)
+    )]
+    fn deserialize_u128(value: u128, expected: Option<PartValue>) {
+        let deserializer = de::value::U128Deserializer::<de::value::Error>::new(value);
+        assert_eq!(PartValue::deserialize(deserializer).ok(), expected);
+    }
+
+    #[rstest(
+        part,
+        expected,
+        case(1, Some(Solution::new().with_part("Part 1", 12))),
+        case(2, Some(Solution::new().with_part_notes("Part 2", "abc", "note"))),
+        case(0, None),
+        case(3, None)
+    )]
+    fn nth_part(part: usize, expected: Option<Solution>) {
+        let solution = Solution::new()
+            .with_part("Part 1", 12)
+            .with_part_notes("Part 2", "abc", "note");
+        assert_eq!(solution.nth_part(part), expected);
+    }
+
+    #[rstest(
+        text,
+        expected,
+        case("Part 1: 2\nPart 2: 966", Solution::new().with_part("Part 1", 2).with_part("Part 2", 966)),
+        case("Part 1: 5\nPart 2: a,b", Solution::new().with_part("Part 1", 5).with_part("Part 2", "a,b")),
+        case("Part 1: 5\n##\n#.", Solution::new().with_part_notes("Part 1", 5, "##\n#.")),
+        case("just text", Solution::new().with_part("Result", "just text"))
+    )]
+    fn from_text(text: &str, expected: Solution) {
+        assert_eq!(Solution::from_text(text), expected);
+    }
+}
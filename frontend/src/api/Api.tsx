@@ -27,10 +27,19 @@ export type ChallengeInfo = {
     description: string
 }
 
+type GroupUnavailable = {
+    group: string
+    message: string
+}
+
+// Rejects with the error of the group when its challenges can't be listed
 export async function getGroupInfo(key: string): Promise<GroupInfo> {
-    return await fetch(`${API_URL}/groups/${key}`)
-        .then(res => res.json())
-        .catch(console.log);
+    const res = await fetch(`${API_URL}/groups/${key}`);
+    if (res.status === 502) {
+        const error: GroupUnavailable = await res.json();
+        throw new Error(`${error.group} is unavailable: ${error.message}`);
+    }
+    return await res.json();
 }
 
 export async function getChallengeInfo(groupKey: string, challengeKey: string): Promise<ChallengeInfo> {
@@ -39,10 +48,50 @@ export async function getChallengeInfo(groupKey: string, challengeKey: string):
         .catch(console.log);
 }
 
+export type SolutionPart = {
+    name: string
+    value: number | string
+    notes?: string
+}
+
+export type Solution = {
+    parts: SolutionPart[]
+}
+
+export function formatSolution(solution: Solution): string {
+    return solution.parts
+        .map(part => `${part.name}: ${part.value}` + (part.notes ? ` (${part.notes})` : ""))
+        .join("\n");
+}
+
+type Job = {
+    id: number
+    status: "queued" | "running" | "done" | "failed" | "cancelled"
+    solution?: Solution
+    error?: string
+}
+
+const JOB_POLL_INTERVAL_MS = 500;
+
+async function waitForJob(job: Job): Promise<string> {
+    while (job.status === "queued" || job.status === "running") {
+        await new Promise(resolve => setTimeout(resolve, JOB_POLL_INTERVAL_MS));
+        job = await fetch(`${API_URL}/jobs/${job.id}`).then(res => res.json());
+    }
+    switch (job.status) {
+        case "done":
+            return formatSolution(job.solution!);
+        case "failed":
+            return job.error ?? "Failed";
+        default:
+            return "Cancelled";
+    }
+}
+
 export async function solveChallenge(groupKey: string, challengeKey: string, data: string): Promise<string> {
     return await fetch(`${API_URL}/groups/${groupKey}/${challengeKey}/solve`, {
         method: 'POST',
         body: data,
     })
-        .then(res => res.text());
-}
\ No newline at end of file
+        .then(res => res.ok ? res.json().then(waitForJob) : res.text());
+}
@@ -7,6 +7,7 @@ type GroupState = {
     groupKey: string,
     displayName: string
     challenges: GridItem[]
+    error?: string
 }
 
 export default class Group extends Component<RouteComponentProps, GroupState> {
@@ -29,10 +30,13 @@ export default class Group extends Component<RouteComponentProps, GroupState> {
         getGroupInfo(this.state.groupKey).then(data => {
             let challenges = data.challenges.map(i => ({ key: i.key, displayName: i.display_name }))
             this.setState({ displayName: data.name, challenges: challenges })
-        })
+        }).catch(error => this.setState({ error: error.message }))
     }
 
     render() {
+        if (this.state.error) {
+            return <p>{this.state.error}</p>;
+        }
         return <BoxGrid baseUrl={`groups/${this.state.groupKey}`} items={this.state.challenges} />;
     }
 }
\ No newline at end of file